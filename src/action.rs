@@ -0,0 +1,42 @@
+use std::sync::RwLock;
+
+/// What a failed unwrap does after it has been logged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FailureAction {
+    /// Panic, as `std`'s `unwrap()` does. The panic message is empty if the
+    /// `panic-quiet` feature is enabled.
+    Panic,
+    /// Abort the process with [`std::process::abort`].
+    Abort,
+    /// Exit the process with [`std::process::exit`] and the given code.
+    Exit(i32),
+}
+
+#[cfg(feature = "abort")]
+const DEFAULT_ACTION: FailureAction = FailureAction::Abort;
+#[cfg(all(feature = "exit", not(feature = "abort")))]
+const DEFAULT_ACTION: FailureAction = FailureAction::Exit(1);
+#[cfg(not(any(feature = "abort", feature = "exit")))]
+const DEFAULT_ACTION: FailureAction = FailureAction::Panic;
+
+static FAILURE_ACTION: RwLock<FailureAction> = RwLock::new(DEFAULT_ACTION);
+
+/// Sets what failed unwraps do after they have been logged. This applies to
+/// every method of the extension traits that would otherwise panic.
+///
+/// This setting is process-wide. The default is [`FailureAction::Panic`],
+/// or [`FailureAction::Abort`] if the `abort` feature is enabled, or
+/// `FailureAction::Exit(1)` if the `exit` feature is enabled.
+pub fn set_failure_action(action: FailureAction) {
+    *FAILURE_ACTION
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = action;
+}
+
+/// Returns what failed unwraps do after they have been logged. See
+/// [`set_failure_action`].
+pub fn failure_action() -> FailureAction {
+    *FAILURE_ACTION
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
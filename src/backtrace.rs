@@ -0,0 +1,56 @@
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// When to capture a [`Backtrace`] and attach it to the record of a failed
+/// unwrap, as the `backtrace` key.
+///
+/// Backtraces are only captured for failed unwraps that panic.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BacktraceCapture {
+    /// Capture a backtrace if the `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE`
+    /// environment variables enable it, as [`Backtrace::capture`] does.
+    Env,
+    /// Always capture a backtrace.
+    Always,
+    /// Never capture a backtrace.
+    Never,
+}
+
+#[cfg(not(feature = "backtrace"))]
+const DEFAULT_CAPTURE: BacktraceCapture = BacktraceCapture::Env;
+#[cfg(feature = "backtrace")]
+const DEFAULT_CAPTURE: BacktraceCapture = BacktraceCapture::Always;
+
+static BACKTRACE_CAPTURE: AtomicU8 = AtomicU8::new(DEFAULT_CAPTURE as u8);
+
+/// Sets when a backtrace is captured and logged along with a failed unwrap.
+///
+/// This setting is process-wide. The default is [`BacktraceCapture::Env`],
+/// or [`BacktraceCapture::Always`] if the `backtrace` feature is enabled.
+pub fn set_backtrace_capture(capture: BacktraceCapture) {
+    BACKTRACE_CAPTURE.store(capture as u8, Ordering::Relaxed);
+}
+
+/// Returns when a backtrace is captured and logged along with a failed
+/// unwrap. See [`set_backtrace_capture`].
+pub fn backtrace_capture() -> BacktraceCapture {
+    match BACKTRACE_CAPTURE.load(Ordering::Relaxed) {
+        capture if capture == BacktraceCapture::Always as u8 => BacktraceCapture::Always,
+        capture if capture == BacktraceCapture::Never as u8 => BacktraceCapture::Never,
+        _ => BacktraceCapture::Env,
+    }
+}
+
+/// Captures a backtrace, if the current setting calls for one.
+pub(crate) fn capture() -> Option<Backtrace> {
+    let backtrace = match backtrace_capture() {
+        BacktraceCapture::Env => Backtrace::capture(),
+        BacktraceCapture::Always => Backtrace::force_capture(),
+        BacktraceCapture::Never => return None,
+    };
+
+    match backtrace.status() {
+        BacktraceStatus::Captured => Some(backtrace),
+        _ => None,
+    }
+}
@@ -0,0 +1,75 @@
+use std::error::Error;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// How the `chain` key of a failure record renders an error's
+/// [`source()`](std::error::Error::source) chain.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChainStyle {
+    /// All causes on a single line, separated by colons: `a: b: c`.
+    Compact,
+    /// The top-level error on the first line, followed by one indented,
+    /// numbered line per cause.
+    MultiLine,
+}
+
+static CHAIN_STYLE: AtomicU8 = AtomicU8::new(ChainStyle::Compact as u8);
+
+/// Sets how the `_chain` methods render an error's source chain.
+///
+/// This setting is process-wide. The default is [`ChainStyle::Compact`].
+pub fn set_chain_style(style: ChainStyle) {
+    CHAIN_STYLE.store(style as u8, Ordering::Relaxed);
+}
+
+/// Returns how the `_chain` methods render an error's source chain. See
+/// [`set_chain_style`].
+pub fn chain_style() -> ChainStyle {
+    match CHAIN_STYLE.load(Ordering::Relaxed) {
+        style if style == ChainStyle::MultiLine as u8 => ChainStyle::MultiLine,
+        _ => ChainStyle::Compact,
+    }
+}
+
+/// Keys for the individual causes of an error, numbered from its first
+/// `source()` as in [`ChainStyle::MultiLine`]. Causes past the last key are
+/// still part of the combined `chain` key.
+const CAUSE_KEYS: [&str; 16] = [
+    "cause.0", "cause.1", "cause.2", "cause.3", "cause.4", "cause.5", "cause.6", "cause.7",
+    "cause.8", "cause.9", "cause.10", "cause.11", "cause.12", "cause.13", "cause.14", "cause.15",
+];
+
+/// An error and every error in its source chain.
+#[derive(Clone, Copy)]
+pub(crate) struct Chain<'a>(pub &'a (dyn Error + 'a));
+
+impl<'a> Chain<'a> {
+    fn iter(self) -> impl Iterator<Item = &'a (dyn Error + 'a)> {
+        std::iter::successors(Some(self.0), |&err| err.source())
+    }
+
+    /// Emits the combined chain, followed by one key per cause, leaving out
+    /// the error itself.
+    pub(crate) fn serialize(self, serializer: &mut dyn slog::Serializer) -> slog::Result {
+        serializer.emit_arguments("chain", &format_args!("{}", self))?;
+        for (key, cause) in CAUSE_KEYS.iter().zip(self.iter().skip(1)) {
+            serializer.emit_arguments(key, &format_args!("{}", cause))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Chain<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let style = chain_style();
+        for (index, cause) in self.iter().enumerate() {
+            match (index, style) {
+                (0, _) => write!(f, "{}", cause)?,
+                (1, ChainStyle::MultiLine) => write!(f, "\n\nCaused by:\n    0: {}", cause)?,
+                (_, ChainStyle::MultiLine) => write!(f, "\n    {}: {}", index - 1, cause)?,
+                (_, ChainStyle::Compact) => write!(f, ": {}", cause)?,
+            }
+        }
+        Ok(())
+    }
+}
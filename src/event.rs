@@ -0,0 +1,132 @@
+use crate::kv::KeyValues;
+use crate::level::IntoLevel;
+use std::sync::OnceLock;
+
+/// Returns the logger that failed unwraps are logged to by the
+/// [`tracing_events::ResultExt`](crate::tracing_events::ResultExt) and
+/// [`tracing_events::OptionExt`](crate::tracing_events::OptionExt) traits.
+pub(crate) fn logger() -> &'static slog::Logger {
+    static LOGGER: OnceLock<slog::Logger> = OnceLock::new();
+    LOGGER.get_or_init(|| slog::Logger::root(EventDrain, slog::o!()))
+}
+
+/// A drain that emits records as `tracing` events, within the current span.
+///
+/// As the target of an event must be known at compile time, events have the
+/// `slog_unwrap` target, rather than the module of the failed unwrap.
+///
+/// The details of a failed unwrap become the event's `value`, `method`,
+/// `variant`, `chain`, `backtrace`, `expr` and `function` fields, and the
+/// record's location its `location` field. Any other key-value pairs are
+/// formatted together into a `kv` field.
+struct EventDrain;
+
+/// Emits an event at a level only known at runtime.
+macro_rules! event {
+    ($level:expr, $($fields:tt)+) => {
+        match $level {
+            slog::Level::Critical | slog::Level::Error => {
+                tracing::event!(target: "slog_unwrap", tracing::Level::ERROR, $($fields)+)
+            }
+            slog::Level::Warning => {
+                tracing::event!(target: "slog_unwrap", tracing::Level::WARN, $($fields)+)
+            }
+            slog::Level::Info => {
+                tracing::event!(target: "slog_unwrap", tracing::Level::INFO, $($fields)+)
+            }
+            slog::Level::Debug => {
+                tracing::event!(target: "slog_unwrap", tracing::Level::DEBUG, $($fields)+)
+            }
+            slog::Level::Trace => {
+                tracing::event!(target: "slog_unwrap", tracing::Level::TRACE, $($fields)+)
+            }
+        }
+    };
+}
+
+impl slog::Drain for EventDrain {
+    type Ok = ();
+    type Err = slog::Never;
+
+    fn log(&self, record: &slog::Record, values: &slog::OwnedKVList) -> Result<(), slog::Never> {
+        let fields = Fields::from(KeyValues::of(record, values));
+
+        let location = format!("{}:{}:{}", record.file(), record.line(), record.column());
+        let kv = as_display(&fields.kv);
+        event!(
+            record.level(),
+            value = as_display(&fields.value),
+            method = as_display(&fields.method),
+            variant = as_display(&fields.variant),
+            chain = as_display(&fields.chain),
+            backtrace = as_display(&fields.backtrace),
+            expr = as_display(&fields.expr),
+            function = as_display(&fields.function),
+            kv,
+            location = %location,
+            "{}",
+            record.msg()
+        );
+        Ok(())
+    }
+}
+
+impl IntoLevel for tracing::Level {
+    fn into_level(self) -> slog::Level {
+        match self {
+            tracing::Level::ERROR => slog::Level::Error,
+            tracing::Level::WARN => slog::Level::Warning,
+            tracing::Level::INFO => slog::Level::Info,
+            tracing::Level::DEBUG => slog::Level::Debug,
+            tracing::Level::TRACE => slog::Level::Trace,
+        }
+    }
+}
+
+/// Records a field with its [`fmt::Display`] output, so that strings are not
+/// quoted.
+fn as_display(field: &Option<String>) -> Option<tracing::field::DisplayValue<&str>> {
+    field.as_deref().map(tracing::field::display)
+}
+
+/// The key-value pairs of a record, sorted into the event's fields.
+#[derive(Default)]
+struct Fields {
+    value: Option<String>,
+    method: Option<String>,
+    variant: Option<String>,
+    chain: Option<String>,
+    backtrace: Option<String>,
+    expr: Option<String>,
+    function: Option<String>,
+    kv: Option<String>,
+}
+
+impl From<KeyValues> for Fields {
+    fn from(kv: KeyValues) -> Self {
+        let mut fields = Fields::default();
+        for (key, value) in kv.0 {
+            let field = match key.as_str() {
+                "value" => &mut fields.value,
+                "method" => &mut fields.method,
+                "variant" => &mut fields.variant,
+                "chain" => &mut fields.chain,
+                "backtrace" => &mut fields.backtrace,
+                "expr" => &mut fields.expr,
+                "function" => &mut fields.function,
+                // Already part of `chain` and `function`.
+                key if key.starts_with("cause.") || key == "module" => continue,
+                key => {
+                    let kv = fields.kv.get_or_insert_with(String::new);
+                    if !kv.is_empty() {
+                        kv.push_str(", ");
+                    }
+                    kv.push_str(&format!("{}: {}", key, value));
+                    continue;
+                }
+            };
+            *field = Some(value);
+        }
+        fields
+    }
+}
@@ -0,0 +1,1344 @@
+/// Defines a pair of extension traits for `Result` and `Option`, logging
+/// failed unwraps to `target`.
+///
+/// With `logger`, every method takes a `log` argument of a type implementing
+/// [`AsLogger`](crate::AsLogger), which `target` can refer to. Without it,
+/// the methods take no logger.
+///
+/// `to` completes the method docs, e.g. "logging the passed message ...
+/// to a scoped [`slog::Logger`]". `level` is the level type taken by the
+/// `_at` methods, `log_err` and `log_none`, converted with
+/// [`IntoLevel`](crate::level::IntoLevel). `kv` is the trait bound of the
+/// pairs taken by the `_kv` methods, followed by the [`Failure`] builder method
+/// that attaches them, and `kv_doc` describes them.
+///
+/// [`Failure`]: crate::Failure
+macro_rules! ext_traits {
+    (
+        $(#[$result_attr:meta])*
+        pub trait $ResultExt:ident;
+
+        $(#[$option_attr:meta])*
+        pub trait $OptionExt:ident;
+
+        $(logger: $log:ident: $L:ident,)?
+        target: $target:expr,
+        to: $to:literal,
+        level: $Level:ty,
+        kv: $KV:path => $with_kv:ident,
+        kv_doc: $kv_doc:literal,
+    ) => {
+        //
+        // Extension trait for Result types.
+        //
+
+        $(#[$result_attr])*
+        pub trait $ResultExt<T, E> {
+            /// Unwraps a result, yielding the content of an [`Ok`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is an [`Err`], logging a message provided by the
+            #[doc = concat!(" [`Err`]'s value ", $to, " at the [default level].")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn unwrap_or_log<$($L)?>(self $(, $log: $L)?) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug;
+
+            /// Unwraps a result, yielding the content of an [`Ok`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is an [`Err`], logging the passed message and the
+            #[doc = concat!(" content of the [`Err`] ", $to, " at the [default level].")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn expect_or_log<$($L)?>(self $(, $log: $L)?, msg: &str) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug;
+
+            /// Unwraps a result, yielding the content of an [`Err`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is an [`Ok`], logging a message provided by the
+            #[doc = concat!(" [`Ok`]'s value ", $to, " at the [default level].")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn unwrap_err_or_log<$($L)?>(self $(, $log: $L)?) -> E
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug;
+
+            /// Unwraps a result, yielding the content of an [`Err`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is an [`Ok`], logging the passed message and the
+            #[doc = concat!(" content of the [`Ok`] ", $to, " at the [default level].")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn expect_err_or_log<$($L)?>(self $(, $log: $L)?, msg: &str) -> E
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug;
+
+            /// Like [`unwrap_or_log`](Self::unwrap_or_log), but logs at the given
+            /// `level`.
+            fn unwrap_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug;
+
+            /// Like [`expect_or_log`](Self::expect_or_log), but logs at the given
+            /// `level`.
+            fn expect_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level, msg: &str) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug;
+
+            /// Like [`unwrap_err_or_log`](Self::unwrap_err_or_log), but logs at the
+            /// given `level`.
+            fn unwrap_err_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level) -> E
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug;
+
+            /// Like [`expect_err_or_log`](Self::expect_err_or_log), but logs at the
+            /// given `level`.
+            fn expect_err_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level, msg: &str) -> E
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug;
+
+            /// Unwraps a result, yielding the content of an [`Ok`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is an [`Err`], logging the [`Err`]'s value and its
+            #[doc = concat!(" whole [`source()`] chain ", $to, " at the [default level].")]
+            /// The chain is logged as a single `chain` key, rendered according to the
+            /// [chain style], and as one `cause.N` key per cause, from `cause.0` for
+            /// the first `source()` up to `cause.15`.
+            ///
+            /// [`source()`]: std::error::Error::source
+            /// [default level]: crate::set_default_level
+            /// [chain style]: crate::set_chain_style
+            fn unwrap_or_log_chain<$($L)?>(self $(, $log: $L)?) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::error::Error;
+
+            /// Unwraps a result, yielding the content of an [`Ok`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is an [`Err`], logging the passed message, the
+            /// [`Err`]'s value and its whole [`source()`] chain
+            #[doc = concat!(" ", $to, " at the [default level]. See")]
+            /// [`unwrap_or_log_chain`](Self::unwrap_or_log_chain).
+            ///
+            /// [`source()`]: std::error::Error::source
+            /// [default level]: crate::set_default_level
+            fn expect_or_log_chain<$($L)?>(self $(, $log: $L)?, msg: &str) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::error::Error;
+
+            /// Unwraps a result, yielding the content of an [`Ok`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is an [`Err`], logging a message provided by the
+            /// [`Err`]'s [`Display`](std::fmt::Display) output
+            #[doc = concat!(" ", $to, " at the [default level].")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn unwrap_or_log_display<$($L)?>(self $(, $log: $L)?) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Display;
+
+            /// Unwraps a result, yielding the content of an [`Ok`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is an [`Err`], logging the passed message and the
+            /// [`Err`]'s [`Display`](std::fmt::Display) output
+            #[doc = concat!(" ", $to, " at the [default level].")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn expect_or_log_display<$($L)?>(self $(, $log: $L)?, msg: &str) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Display;
+
+            /// Unwraps a result, yielding the content of an [`Ok`].
+            ///
+            /// Unlike [`expect_or_log`](Self::expect_or_log), the message is only
+            /// built, by calling `f`, if the unwrap fails.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is an [`Err`], logging the message returned by `f`
+            #[doc = concat!(" and the content of the [`Err`] ", $to, " at the [default level].")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn expect_or_log_with<$($L,)? M, F>(self $(, $log: $L)?, f: F) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+                M: ::std::fmt::Display,
+                F: FnOnce() -> M;
+
+            /// Converts a result into an [`Option`], discarding the error.
+            ///
+            /// Never panics. If the value is an [`Err`], logs a message provided by
+            #[doc = concat!(" the [`Err`]'s value ", $to, " at the [default level], and")]
+            /// returns [`None`].
+            ///
+            /// [default level]: crate::set_default_level
+            fn ok_or_log<$($L)?>(self $(, $log: $L)?) -> Option<T>
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug;
+
+            /// Returns the result unchanged.
+            ///
+            /// Never panics. If the value is an [`Err`], logs a message provided by
+            #[doc = concat!(" the [`Err`]'s value ", $to, " at the given `level` first.")]
+            fn log_err<$($L)?>(self $(, $log: $L)?, level: $Level) -> Result<T, E>
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug;
+
+            /// Returns the contained [`Ok`] value or a default.
+            ///
+            /// Never panics. If the value is an [`Err`], logs a message provided by
+            #[doc = concat!(" the [`Err`]'s value ", $to, " at the [fallback level], and")]
+            /// returns the default value for `T`.
+            ///
+            /// [fallback level]: crate::set_fallback_level
+            fn unwrap_or_default_log<$($L)?>(self $(, $log: $L)?) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+                T: Default;
+
+            /// Returns the contained [`Ok`] value or computes it from a closure.
+            ///
+            /// Never panics. If the value is an [`Err`], logs a message provided by
+            #[doc = concat!(" the [`Err`]'s value ", $to, " at the [fallback level], and")]
+            /// returns the result of calling `op` with the [`Err`]'s value.
+            ///
+            /// [fallback level]: crate::set_fallback_level
+            fn unwrap_or_log_else<$($L,)? F>(self $(, $log: $L)?, op: F) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+                F: FnOnce(E) -> T;
+
+            /// Returns the contained [`Ok`] value or the provided fallback.
+            ///
+            /// Never panics. If the value is an [`Err`], logs a message provided by
+            #[doc = concat!(" the [`Err`]'s value ", $to, " at the [fallback level], and")]
+            /// returns `fallback`.
+            ///
+            /// [fallback level]: crate::set_fallback_level
+            fn unwrap_or_log_value<$($L)?>(self $(, $log: $L)?, fallback: T) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug;
+
+            /// Unwraps a result, yielding the content of an [`Ok`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is an [`Err`], logging a message provided by the
+            #[doc = concat!(" [`Err`]'s value ", $to, " at the [default level], along")]
+            /// with the key-value pairs in `kv`.
+            ///
+            #[doc = concat!(" ", $kv_doc)]
+            ///
+            /// [default level]: crate::set_default_level
+            fn unwrap_or_log_kv<$($L,)? K>(self $(, $log: $L)?, kv: K) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+                K: $KV;
+
+            /// Unwraps a result, yielding the content of an [`Ok`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is an [`Err`], logging the passed message and the
+            #[doc = concat!(" content of the [`Err`] ", $to, " at the [default level],")]
+            /// along with the key-value pairs in `kv`.
+            ///
+            #[doc = concat!(" ", $kv_doc)]
+            ///
+            /// [default level]: crate::set_default_level
+            fn expect_or_log_kv<$($L,)? K>(self $(, $log: $L)?, msg: &str, kv: K) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+                K: $KV;
+        }
+
+        impl<T, E> $ResultExt<T, E> for Result<T, E> {
+            #[inline]
+            #[track_caller]
+            fn unwrap_or_log<$($L)?>(self $(, $log: $L)?) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+            {
+                match self {
+                    Ok(t) => t,
+                    Err(e) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "unwrap_or_log",
+                            "Err",
+                            &"called `Result::unwrap_or_log()` on an `Err` value",
+                        )
+                        .with_value(&e),
+                    ),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_or_log<$($L)?>(self $(, $log: $L)?, msg: &str) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+            {
+                match self {
+                    Ok(t) => t,
+                    Err(e) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "expect_or_log",
+                            "Err",
+                            &msg,
+                        )
+                        .with_value(&e),
+                    ),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_err_or_log<$($L)?>(self $(, $log: $L)?) -> E
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug,
+            {
+                match self {
+                    Ok(t) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "unwrap_err_or_log",
+                            "Ok",
+                            &"called `Result::unwrap_err_or_log()` on an `Ok` value",
+                        )
+                        .with_value(&t),
+                    ),
+                    Err(e) => e,
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_err_or_log<$($L)?>(self $(, $log: $L)?, msg: &str) -> E
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug,
+            {
+                match self {
+                    Ok(t) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "expect_err_or_log",
+                            "Ok",
+                            &msg,
+                        )
+                        .with_value(&t),
+                    ),
+                    Err(e) => e,
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+            {
+                match self {
+                    Ok(t) => t,
+                    Err(e) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::IntoLevel::into_level(level),
+                            "unwrap_or_log_at",
+                            "Err",
+                            &"called `Result::unwrap_or_log_at()` on an `Err` value",
+                        )
+                        .with_value(&e),
+                    ),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level, msg: &str) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+            {
+                match self {
+                    Ok(t) => t,
+                    Err(e) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::IntoLevel::into_level(level),
+                            "expect_or_log_at",
+                            "Err",
+                            &msg,
+                        )
+                        .with_value(&e),
+                    ),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_err_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level) -> E
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug,
+            {
+                match self {
+                    Ok(t) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::IntoLevel::into_level(level),
+                            "unwrap_err_or_log_at",
+                            "Ok",
+                            &"called `Result::unwrap_err_or_log_at()` on an `Ok` value",
+                        )
+                        .with_value(&t),
+                    ),
+                    Err(e) => e,
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_err_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level, msg: &str) -> E
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug,
+            {
+                match self {
+                    Ok(t) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::IntoLevel::into_level(level),
+                            "expect_err_or_log_at",
+                            "Ok",
+                            &msg,
+                        )
+                        .with_value(&t),
+                    ),
+                    Err(e) => e,
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_or_log_chain<$($L)?>(self $(, $log: $L)?) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::error::Error,
+            {
+                match self {
+                    Ok(t) => t,
+                    Err(e) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "unwrap_or_log_chain",
+                            "Err",
+                            &"called `Result::unwrap_or_log_chain()` on an `Err` value",
+                        )
+                        .with_value(&e)
+                        .with_chain(&e),
+                    ),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_or_log_chain<$($L)?>(self $(, $log: $L)?, msg: &str) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::error::Error,
+            {
+                match self {
+                    Ok(t) => t,
+                    Err(e) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "expect_or_log_chain",
+                            "Err",
+                            &msg,
+                        )
+                        .with_value(&e)
+                        .with_chain(&e),
+                    ),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_or_log_display<$($L)?>(self $(, $log: $L)?) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Display,
+            {
+                match self {
+                    Ok(t) => t,
+                    Err(e) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "unwrap_or_log_display",
+                            "Err",
+                            &"called `Result::unwrap_or_log_display()` on an `Err` value",
+                        )
+                        .with_display(&e),
+                    ),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_or_log_display<$($L)?>(self $(, $log: $L)?, msg: &str) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Display,
+            {
+                match self {
+                    Ok(t) => t,
+                    Err(e) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "expect_or_log_display",
+                            "Err",
+                            &msg,
+                        )
+                        .with_display(&e),
+                    ),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_or_log_with<$($L,)? M, F>(self $(, $log: $L)?, f: F) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+                M: ::std::fmt::Display,
+                F: FnOnce() -> M,
+            {
+                match self {
+                    Ok(t) => t,
+                    Err(e) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "expect_or_log_with",
+                            "Err",
+                            &f(),
+                        )
+                        .with_value(&e),
+                    ),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn ok_or_log<$($L)?>(self $(, $log: $L)?) -> Option<T>
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+            {
+                match self {
+                    Ok(t) => Some(t),
+                    Err(e) => {
+                        $crate::failure::report(
+                            $crate::failure::Failure::new(
+                                $target,
+                                $crate::level::default_level(),
+                                "ok_or_log",
+                                "Err",
+                                &"called `Result::ok_or_log()` on an `Err` value",
+                            )
+                            .with_value(&e),
+                        );
+                        None
+                    }
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn log_err<$($L)?>(self $(, $log: $L)?, level: $Level) -> Result<T, E>
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+            {
+                if let Err(e) = &self {
+                    $crate::failure::report(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::IntoLevel::into_level(level),
+                            "log_err",
+                            "Err",
+                            &"called `Result::log_err()` on an `Err` value",
+                        )
+                        .with_value(e),
+                    );
+                }
+                self
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_or_default_log<$($L)?>(self $(, $log: $L)?) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+                T: Default,
+            {
+                match self {
+                    Ok(t) => t,
+                    Err(e) => {
+                        $crate::failure::report(
+                            $crate::failure::Failure::new(
+                                $target,
+                                $crate::level::fallback_level(),
+                                "unwrap_or_default_log",
+                                "Err",
+                                &"called `Result::unwrap_or_default_log()` on an `Err` value",
+                            )
+                            .with_value(&e),
+                        );
+                        T::default()
+                    }
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_or_log_else<$($L,)? F>(self $(, $log: $L)?, op: F) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+                F: FnOnce(E) -> T,
+            {
+                match self {
+                    Ok(t) => t,
+                    Err(e) => {
+                        $crate::failure::report(
+                            $crate::failure::Failure::new(
+                                $target,
+                                $crate::level::fallback_level(),
+                                "unwrap_or_log_else",
+                                "Err",
+                                &"called `Result::unwrap_or_log_else()` on an `Err` value",
+                            )
+                            .with_value(&e),
+                        );
+                        op(e)
+                    }
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_or_log_value<$($L)?>(self $(, $log: $L)?, fallback: T) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+            {
+                match self {
+                    Ok(t) => t,
+                    Err(e) => {
+                        $crate::failure::report(
+                            $crate::failure::Failure::new(
+                                $target,
+                                $crate::level::fallback_level(),
+                                "unwrap_or_log_value",
+                                "Err",
+                                &"called `Result::unwrap_or_log_value()` on an `Err` value",
+                            )
+                            .with_value(&e),
+                        );
+                        fallback
+                    }
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_or_log_kv<$($L,)? K>(self $(, $log: $L)?, kv: K) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+                K: $KV,
+            {
+                match self {
+                    Ok(t) => t,
+                    Err(e) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "unwrap_or_log_kv",
+                            "Err",
+                            &"called `Result::unwrap_or_log_kv()` on an `Err` value",
+                        )
+                        .with_value(&e)
+                        .$with_kv(&kv),
+                    ),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_or_log_kv<$($L,)? K>(self $(, $log: $L)?, msg: &str, kv: K) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                E: ::std::fmt::Debug,
+                K: $KV,
+            {
+                match self {
+                    Ok(t) => t,
+                    Err(e) => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "expect_or_log_kv",
+                            "Err",
+                            &msg,
+                        )
+                        .with_value(&e)
+                        .$with_kv(&kv),
+                    ),
+                }
+            }
+        }
+
+        //
+        // Extension trait for Option types.
+        //
+
+        $(#[$option_attr])*
+        pub trait $OptionExt<T> {
+            /// Moves the value `v` out of the `Option<T>` if it is
+            /// [`Some(v)`](Some).
+            ///
+            /// In general, because this function may panic, its use is discouraged.
+            /// Instead, prefer to use pattern matching and handle the [`None`]
+            /// case explicitly.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the self value equals [`None`], logging an error message
+            #[doc = concat!(" ", $to, " at the [default level].")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn unwrap_or_log<$($L)?>(self $(, $log: $L)?) -> T
+            where
+                $($L: $crate::AsLogger,)?;
+
+            /// Unwraps an option, yielding the content of a [`Some`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is a [`None`], logging the passed message
+            #[doc = concat!(" ", $to, " at the [default level].")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn expect_or_log<$($L)?>(self $(, $log: $L)?, msg: &str) -> T
+            where
+                $($L: $crate::AsLogger,)?;
+
+            /// Unwraps an option, expecting [`None`] and returning nothing.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is a [`Some`], logging a message derived from the
+            #[doc = concat!(" [`Some`]'s value ", $to, " at the [default level].")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn unwrap_none_or_log<$($L)?>(self $(, $log: $L)?)
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug;
+
+            /// Unwraps an option, expecting [`None`] and returning nothing.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is a [`Some`], logging the passed message and the
+            #[doc = concat!(" content of the [`Some`] ", $to, " at the [default level].")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn expect_none_or_log<$($L)?>(self $(, $log: $L)?, msg: &str)
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug;
+
+            /// Like [`unwrap_or_log`](Self::unwrap_or_log), but logs at the given
+            /// `level`.
+            fn unwrap_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level) -> T
+            where
+                $($L: $crate::AsLogger,)?;
+
+            /// Like [`expect_or_log`](Self::expect_or_log), but logs at the given
+            /// `level`.
+            fn expect_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level, msg: &str) -> T
+            where
+                $($L: $crate::AsLogger,)?;
+
+            /// Like [`unwrap_none_or_log`](Self::unwrap_none_or_log), but logs at the
+            /// given `level`.
+            fn unwrap_none_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level)
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug;
+
+            /// Like [`expect_none_or_log`](Self::expect_none_or_log), but logs at the
+            /// given `level`.
+            fn expect_none_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level, msg: &str)
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug;
+
+            /// Unwraps an option, expecting [`None`] and returning nothing.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is a [`Some`], logging a message derived from the
+            /// [`Some`]'s [`Display`](std::fmt::Display) output
+            #[doc = concat!(" ", $to, " at the [default level].")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn unwrap_none_or_log_display<$($L)?>(self $(, $log: $L)?)
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Display;
+
+            /// Unwraps an option, expecting [`None`] and returning nothing.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is a [`Some`], logging the passed message and the
+            /// [`Some`]'s [`Display`](std::fmt::Display) output
+            #[doc = concat!(" ", $to, " at the [default level].")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn expect_none_or_log_display<$($L)?>(self $(, $log: $L)?, msg: &str)
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Display;
+
+            /// Unwraps an option, yielding the content of a [`Some`].
+            ///
+            /// Unlike [`expect_or_log`](Self::expect_or_log), the message is only
+            /// built, by calling `f`, if the unwrap fails.
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is a [`None`], logging the message returned by `f`
+            #[doc = concat!(" ", $to, " at the [default level].")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn expect_or_log_with<$($L,)? M, F>(self $(, $log: $L)?, f: F) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                M: ::std::fmt::Display,
+                F: FnOnce() -> M;
+
+            /// Returns the option unchanged.
+            ///
+            /// Never panics. If the value is a [`None`], logs an error message
+            #[doc = concat!(" ", $to, " at the given `level` first.")]
+            fn log_none<$($L)?>(self $(, $log: $L)?, level: $Level) -> Option<T>
+            where
+                $($L: $crate::AsLogger,)?;
+
+            /// Returns the option unchanged, expecting it to be [`None`].
+            ///
+            /// Never panics. If the value is a [`Some`], logs a message derived from
+            #[doc = concat!(" the [`Some`]'s value ", $to, " at the [default level] first.")]
+            ///
+            /// [default level]: crate::set_default_level
+            fn none_or_log<$($L)?>(self $(, $log: $L)?) -> Option<T>
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug;
+
+            /// Returns the contained [`Some`] value or a default.
+            ///
+            /// Never panics. If the value is a [`None`], logs an error message
+            #[doc = concat!(" ", $to, " at the [fallback level], and returns the default")]
+            /// value for `T`.
+            ///
+            /// [fallback level]: crate::set_fallback_level
+            fn unwrap_or_default_log<$($L)?>(self $(, $log: $L)?) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                T: Default;
+
+            /// Returns the contained [`Some`] value or computes it from a closure.
+            ///
+            /// Never panics. If the value is a [`None`], logs an error message
+            #[doc = concat!(" ", $to, " at the [fallback level], and returns the result of")]
+            /// calling `f`.
+            ///
+            /// [fallback level]: crate::set_fallback_level
+            fn unwrap_or_log_else<$($L,)? F>(self $(, $log: $L)?, f: F) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                F: FnOnce() -> T;
+
+            /// Returns the contained [`Some`] value or the provided fallback.
+            ///
+            /// Never panics. If the value is a [`None`], logs an error message
+            #[doc = concat!(" ", $to, " at the [fallback level], and returns `fallback`.")]
+            ///
+            /// [fallback level]: crate::set_fallback_level
+            fn unwrap_or_log_value<$($L)?>(self $(, $log: $L)?, fallback: T) -> T
+            where
+                $($L: $crate::AsLogger,)?;
+
+            /// Unwraps an option, yielding the content of a [`Some`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is a [`None`], logging an error message
+            #[doc = concat!(" ", $to, " at the [default level], along with the key-value")]
+            /// pairs in `kv`.
+            ///
+            #[doc = concat!(" ", $kv_doc)]
+            ///
+            /// [default level]: crate::set_default_level
+            fn unwrap_or_log_kv<$($L,)? K>(self $(, $log: $L)?, kv: K) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                K: $KV;
+
+            /// Unwraps an option, yielding the content of a [`Some`].
+            ///
+            /// # Panics
+            ///
+            /// Panics if the value is a [`None`], logging the passed message
+            #[doc = concat!(" ", $to, " at the [default level], along with the key-value")]
+            /// pairs in `kv`.
+            ///
+            #[doc = concat!(" ", $kv_doc)]
+            ///
+            /// [default level]: crate::set_default_level
+            fn expect_or_log_kv<$($L,)? K>(self $(, $log: $L)?, msg: &str, kv: K) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                K: $KV;
+        }
+
+        impl<T> $OptionExt<T> for Option<T> {
+            #[inline]
+            #[track_caller]
+            fn unwrap_or_log<$($L)?>(self $(, $log: $L)?) -> T
+            where
+                $($L: $crate::AsLogger,)?
+            {
+                match self {
+                    Some(val) => val,
+                    None => $crate::failure::failed($crate::failure::Failure::new(
+                        $target,
+                        $crate::level::default_level(),
+                        "unwrap_or_log",
+                        "None",
+                        &"called `Option::unwrap_or_log()` on a `None` value",
+                    )),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_or_log<$($L)?>(self $(, $log: $L)?, msg: &str) -> T
+            where
+                $($L: $crate::AsLogger,)?
+            {
+                match self {
+                    Some(val) => val,
+                    None => $crate::failure::failed($crate::failure::Failure::new(
+                        $target,
+                        $crate::level::default_level(),
+                        "expect_or_log",
+                        "None",
+                        &msg,
+                    )),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_none_or_log<$($L)?>(self $(, $log: $L)?)
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug,
+            {
+                if let Some(val) = self {
+                    $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "unwrap_none_or_log",
+                            "Some",
+                            &"called `Option::unwrap_none_or_log()` on a `Some` value",
+                        )
+                        .with_value(&val),
+                    );
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_none_or_log<$($L)?>(self $(, $log: $L)?, msg: &str)
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug,
+            {
+                if let Some(val) = self {
+                    $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "expect_none_or_log",
+                            "Some",
+                            &msg,
+                        )
+                        .with_value(&val),
+                    );
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level) -> T
+            where
+                $($L: $crate::AsLogger,)?
+            {
+                match self {
+                    Some(val) => val,
+                    None => $crate::failure::failed($crate::failure::Failure::new(
+                        $target,
+                        $crate::level::IntoLevel::into_level(level),
+                        "unwrap_or_log_at",
+                        "None",
+                        &"called `Option::unwrap_or_log_at()` on a `None` value",
+                    )),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level, msg: &str) -> T
+            where
+                $($L: $crate::AsLogger,)?
+            {
+                match self {
+                    Some(val) => val,
+                    None => $crate::failure::failed($crate::failure::Failure::new(
+                        $target,
+                        $crate::level::IntoLevel::into_level(level),
+                        "expect_or_log_at",
+                        "None",
+                        &msg,
+                    )),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_none_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level)
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug,
+            {
+                if let Some(val) = self {
+                    $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::IntoLevel::into_level(level),
+                            "unwrap_none_or_log_at",
+                            "Some",
+                            &"called `Option::unwrap_none_or_log_at()` on a `Some` value",
+                        )
+                        .with_value(&val),
+                    );
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_none_or_log_at<$($L)?>(self $(, $log: $L)?, level: $Level, msg: &str)
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug,
+            {
+                if let Some(val) = self {
+                    $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::IntoLevel::into_level(level),
+                            "expect_none_or_log_at",
+                            "Some",
+                            &msg,
+                        )
+                        .with_value(&val),
+                    );
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_none_or_log_display<$($L)?>(self $(, $log: $L)?)
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Display,
+            {
+                if let Some(val) = self {
+                    $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "unwrap_none_or_log_display",
+                            "Some",
+                            &"called `Option::unwrap_none_or_log_display()` on a `Some` value",
+                        )
+                        .with_display(&val),
+                    );
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_none_or_log_display<$($L)?>(self $(, $log: $L)?, msg: &str)
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Display,
+            {
+                if let Some(val) = self {
+                    $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "expect_none_or_log_display",
+                            "Some",
+                            &msg,
+                        )
+                        .with_display(&val),
+                    );
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_or_log_with<$($L,)? M, F>(self $(, $log: $L)?, f: F) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                M: ::std::fmt::Display,
+                F: FnOnce() -> M,
+            {
+                match self {
+                    Some(val) => val,
+                    None => $crate::failure::failed($crate::failure::Failure::new(
+                        $target,
+                        $crate::level::default_level(),
+                        "expect_or_log_with",
+                        "None",
+                        &f(),
+                    )),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn log_none<$($L)?>(self $(, $log: $L)?, level: $Level) -> Option<T>
+            where
+                $($L: $crate::AsLogger,)?
+            {
+                if self.is_none() {
+                    $crate::failure::report($crate::failure::Failure::new(
+                        $target,
+                        $crate::level::IntoLevel::into_level(level),
+                        "log_none",
+                        "None",
+                        &"called `Option::log_none()` on a `None` value",
+                    ));
+                }
+                self
+            }
+
+            #[inline]
+            #[track_caller]
+            fn none_or_log<$($L)?>(self $(, $log: $L)?) -> Option<T>
+            where
+                $($L: $crate::AsLogger,)?
+                T: ::std::fmt::Debug,
+            {
+                if let Some(val) = &self {
+                    $crate::failure::report(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "none_or_log",
+                            "Some",
+                            &"called `Option::none_or_log()` on a `Some` value",
+                        )
+                        .with_value(val),
+                    );
+                }
+                self
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_or_default_log<$($L)?>(self $(, $log: $L)?) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                T: Default,
+            {
+                match self {
+                    Some(val) => val,
+                    None => {
+                        $crate::failure::report($crate::failure::Failure::new(
+                            $target,
+                            $crate::level::fallback_level(),
+                            "unwrap_or_default_log",
+                            "None",
+                            &"called `Option::unwrap_or_default_log()` on a `None` value",
+                        ));
+                        T::default()
+                    }
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_or_log_else<$($L,)? F>(self $(, $log: $L)?, f: F) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                F: FnOnce() -> T,
+            {
+                match self {
+                    Some(val) => val,
+                    None => {
+                        $crate::failure::report($crate::failure::Failure::new(
+                            $target,
+                            $crate::level::fallback_level(),
+                            "unwrap_or_log_else",
+                            "None",
+                            &"called `Option::unwrap_or_log_else()` on a `None` value",
+                        ));
+                        f()
+                    }
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_or_log_value<$($L)?>(self $(, $log: $L)?, fallback: T) -> T
+            where
+                $($L: $crate::AsLogger,)?
+            {
+                match self {
+                    Some(val) => val,
+                    None => {
+                        $crate::failure::report($crate::failure::Failure::new(
+                            $target,
+                            $crate::level::fallback_level(),
+                            "unwrap_or_log_value",
+                            "None",
+                            &"called `Option::unwrap_or_log_value()` on a `None` value",
+                        ));
+                        fallback
+                    }
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn unwrap_or_log_kv<$($L,)? K>(self $(, $log: $L)?, kv: K) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                K: $KV,
+            {
+                match self {
+                    Some(val) => val,
+                    None => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "unwrap_or_log_kv",
+                            "None",
+                            &"called `Option::unwrap_or_log_kv()` on a `None` value",
+                        )
+                        .$with_kv(&kv),
+                    ),
+                }
+            }
+
+            #[inline]
+            #[track_caller]
+            fn expect_or_log_kv<$($L,)? K>(self $(, $log: $L)?, msg: &str, kv: K) -> T
+            where
+                $($L: $crate::AsLogger,)?
+                K: $KV,
+            {
+                match self {
+                    Some(val) => val,
+                    None => $crate::failure::failed(
+                        $crate::failure::Failure::new(
+                            $target,
+                            $crate::level::default_level(),
+                            "expect_or_log_kv",
+                            "None",
+                            &msg,
+                        )
+                        .$with_kv(&kv),
+                    ),
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use ext_traits;
@@ -0,0 +1,77 @@
+use crate::kv::KeyValues;
+use crate::level::IntoLevel;
+use std::sync::OnceLock;
+
+/// Returns the logger that failed unwraps are logged to by the
+/// [`log_facade::ResultExt`](crate::log_facade::ResultExt) and
+/// [`log_facade::OptionExt`](crate::log_facade::OptionExt) traits.
+pub(crate) fn logger() -> &'static slog::Logger {
+    static LOGGER: OnceLock<slog::Logger> = OnceLock::new();
+    LOGGER.get_or_init(|| slog::Logger::root(LogDrain, slog::o!()))
+}
+
+/// A drain that forwards records to the `log` facade, as if logged with the
+/// `log` macros from the record's location.
+///
+/// The record's module is used as the target, or `slog_unwrap` if unknown.
+/// Its key-value pairs are passed along as `log` key-values, formatted as
+/// strings.
+struct LogDrain;
+
+impl slog::Drain for LogDrain {
+    type Ok = ();
+    type Err = slog::Never;
+
+    fn log(&self, record: &slog::Record, values: &slog::OwnedKVList) -> Result<(), slog::Never> {
+        log(record, values, &None::<(&str, &str)>);
+        Ok(())
+    }
+}
+
+/// Forwards a record to the `log` facade, as [`LogDrain`] does, with the
+/// key-values of `kv` ahead of the record's own.
+pub(crate) fn log(record: &slog::Record, values: &slog::OwnedKVList, kv: &dyn log::kv::Source) {
+    let level = match record.level() {
+        slog::Level::Critical | slog::Level::Error => log::Level::Error,
+        slog::Level::Warning => log::Level::Warn,
+        slog::Level::Info => log::Level::Info,
+        slog::Level::Debug => log::Level::Debug,
+        slog::Level::Trace => log::Level::Trace,
+    };
+    if level > log::STATIC_MAX_LEVEL || level > log::max_level() {
+        return;
+    }
+
+    let mut pairs = KeyValues::default();
+    pairs.push_source(kv);
+    pairs.push_record(record, values);
+    let pairs: Vec<(&str, &str)> = pairs.iter().collect();
+
+    let module = match record.module() {
+        "" => None,
+        module => Some(module),
+    };
+    log::logger().log(
+        &log::Record::builder()
+            .args(*record.msg())
+            .level(level)
+            .target(module.unwrap_or("slog_unwrap"))
+            .module_path_static(module)
+            .file_static(Some(record.file()))
+            .line(Some(record.line()))
+            .key_values(&pairs.as_slice())
+            .build(),
+    );
+}
+
+impl IntoLevel for log::Level {
+    fn into_level(self) -> slog::Level {
+        match self {
+            log::Level::Error => slog::Level::Error,
+            log::Level::Warn => slog::Level::Warning,
+            log::Level::Info => slog::Level::Info,
+            log::Level::Debug => slog::Level::Debug,
+            log::Level::Trace => slog::Level::Trace,
+        }
+    }
+}
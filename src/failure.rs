@@ -0,0 +1,408 @@
+use crate::action::{failure_action, FailureAction};
+use crate::backtrace;
+use crate::chain::Chain;
+use crate::flush;
+use crate::global_logger;
+use crate::handler;
+use crate::hook;
+use std::backtrace::Backtrace;
+use std::error::Error;
+use std::fmt;
+use std::panic::Location;
+use std::process;
+
+/// A logger of any drain type, borrowed as the [`slog::Drain`] it implements,
+/// as returned by [`Failure::logger`].
+pub type LoggerDrain<'a> = dyn slog::Drain<Ok = (), Err = slog::Never> + 'a;
+
+/// Where a failed unwrap is logged to.
+pub(crate) enum Target<'a> {
+    /// An explicitly passed logger.
+    Logger(&'a LoggerDrain<'a>),
+    /// The logger currently set in `slog-scope`.
+    #[cfg(feature = "scope")]
+    Scope,
+    /// The logger set on the current thread with `with_logger`, or else the
+    /// one set with `set_global_logger`, or `stderr`.
+    Global,
+    /// The `log` facade.
+    #[cfg(feature = "log")]
+    Log,
+    /// `tracing` events.
+    #[cfg(feature = "tracing")]
+    Tracing,
+}
+
+impl<'a> Target<'a> {
+    fn into_logger(self) -> TargetLogger<'a> {
+        match self {
+            Target::Logger(log) => TargetLogger::Borrowed(log),
+            #[cfg(feature = "scope")]
+            Target::Scope => TargetLogger::Owned(slog_scope::logger()),
+            Target::Global => match global_logger::thread_logger() {
+                Some(log) => TargetLogger::Owned(log),
+                None => TargetLogger::Borrowed(global_logger::logger()),
+            },
+            #[cfg(feature = "log")]
+            Target::Log => TargetLogger::Borrowed(crate::facade::logger()),
+            #[cfg(feature = "tracing")]
+            Target::Tracing => TargetLogger::Borrowed(crate::event::logger()),
+        }
+    }
+}
+
+/// The logger a failed unwrap is logged to, once its target is resolved.
+enum TargetLogger<'a> {
+    Borrowed(&'a LoggerDrain<'a>),
+    Owned(slog::Logger),
+}
+
+/// Describes a failed unwrap, as passed to a
+/// [`FailureHandler`](crate::FailureHandler).
+pub struct Failure<'a> {
+    logger: TargetLogger<'a>,
+    location: &'static Location<'static>,
+    level: slog::Level,
+    method: &'static str,
+    variant: &'static str,
+    msg: &'a dyn fmt::Display,
+    value: Option<Value<'a>>,
+    chain: Option<Chain<'a>>,
+    backtrace: Option<Backtrace>,
+    kv: &'a dyn slog::KV,
+    #[cfg(feature = "log")]
+    log_kv: Option<&'a dyn log::kv::Source>,
+    #[cfg(feature = "tracing")]
+    fields: Option<&'a dyn crate::tracing_events::Fields>,
+    source: Option<&'a Source>,
+    fatal: bool,
+}
+
+impl<'a> Failure<'a> {
+    #[track_caller]
+    pub(crate) fn new(
+        target: Target<'a>,
+        level: slog::Level,
+        method: &'static str,
+        variant: &'static str,
+        msg: &'a dyn fmt::Display,
+    ) -> Self {
+        Failure {
+            logger: target.into_logger(),
+            location: Location::caller(),
+            level,
+            method,
+            variant,
+            msg,
+            value: None,
+            chain: None,
+            backtrace: None,
+            kv: &(),
+            #[cfg(feature = "log")]
+            log_kv: None,
+            #[cfg(feature = "tracing")]
+            fields: None,
+            source: None,
+            fatal: false,
+        }
+    }
+
+    /// Attaches the offending value, to be formatted with [`fmt::Debug`].
+    pub(crate) fn with_value(mut self, value: &'a dyn fmt::Debug) -> Self {
+        self.value = Some(Value::Debug(value));
+        self
+    }
+
+    /// Attaches the offending value, to be formatted with [`fmt::Display`].
+    pub(crate) fn with_display(mut self, value: &'a dyn fmt::Display) -> Self {
+        self.value = Some(Value::Display(value));
+        self
+    }
+
+    /// Attaches the offending error, logging its whole source chain.
+    pub(crate) fn with_chain(mut self, error: &'a (dyn Error + 'a)) -> Self {
+        self.chain = Some(Chain(error));
+        self
+    }
+
+    /// Attaches key-value pairs describing the context of the failure.
+    pub(crate) fn with_kv(mut self, kv: &'a dyn slog::KV) -> Self {
+        self.kv = kv;
+        self
+    }
+
+    /// Attaches key-value pairs describing the context of the failure, to be
+    /// passed along to the `log` facade.
+    #[cfg(feature = "log")]
+    pub(crate) fn with_log_kv(mut self, kv: &'a dyn log::kv::Source) -> Self {
+        self.log_kv = Some(kv);
+        self
+    }
+
+    /// Attaches key-value pairs describing the context of the failure, given
+    /// to the `tracing` feature's `_kv` methods.
+    #[cfg(feature = "tracing")]
+    pub(crate) fn with_fields(mut self, fields: &'a dyn crate::tracing_events::Fields) -> Self {
+        self.fields = Some(fields);
+        self
+    }
+
+    /// Attaches the source of the expression that was unwrapped.
+    pub(crate) fn with_source(mut self, source: &'a Source) -> Self {
+        self.source = Some(source);
+        self
+    }
+
+    /// The logger the failure is to be logged to.
+    ///
+    /// As loggers can be of any drain type, the logger is returned as the
+    /// [`slog::Drain`] it implements. Records logged to it carry the
+    /// logger's key-value pairs, along with those passed to
+    /// [`log`](slog::Drain::log).
+    ///
+    /// As it isn't a [`slog::Logger`], the `slog` logging macros can't be
+    /// used with it. Records are built with [`slog::record!`] instead:
+    ///
+    /// ```
+    /// use slog::Drain;
+    ///
+    /// slog_unwrap::set_failure_handler(|failure: &slog_unwrap::Failure| {
+    ///     let _ = failure.logger().log(
+    ///         &slog::record!(
+    ///             slog::Level::Warning,
+    ///             "",
+    ///             &format_args!("{} at {}", failure.msg(), failure.location()),
+    ///             slog::b!("method" => failure.method())
+    ///         ),
+    ///         &slog::OwnedKVList::from(slog::o!()),
+    ///     );
+    /// });
+    /// ```
+    pub fn logger(&self) -> &LoggerDrain<'a> {
+        match &self.logger {
+            TargetLogger::Borrowed(log) => *log,
+            TargetLogger::Owned(log) => log,
+        }
+    }
+
+    /// The location of the call that failed.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// The level the failure is to be logged at.
+    pub fn level(&self) -> slog::Level {
+        self.level
+    }
+
+    /// The name of the method that was called, e.g. `unwrap_or_log`.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The variant that was found, e.g. `Err` or `None`.
+    pub fn variant(&self) -> &'static str {
+        self.variant
+    }
+
+    /// The message describing the failure.
+    pub fn msg(&self) -> &dyn fmt::Display {
+        self.msg
+    }
+
+    /// The offending value, e.g. the content of an `Err`, if there is one.
+    pub fn value(&self) -> Option<&dyn fmt::Debug> {
+        self.value.as_ref().map(|value| value as &dyn fmt::Debug)
+    }
+
+    /// The key-value pairs passed along with the failure, such as those of
+    /// [`expect_or_log!`](crate::expect_or_log).
+    ///
+    /// Pairs given to the `log` feature's `_kv` methods as a
+    /// `log::kv::Source` are not included, and are only passed along to the
+    /// `log` facade by [`log`](Failure::log). Nor are those given to the
+    /// `tracing` feature's `_kv` methods, which are only part of the
+    /// [`slog::KV`] implementation.
+    pub fn kv(&self) -> &dyn slog::KV {
+        self.kv
+    }
+
+    /// The source text of the expression that was unwrapped, if known, as
+    /// recorded by [`unwrap_or_log!`](crate::unwrap_or_log) and
+    /// [`expect_or_log!`](crate::expect_or_log).
+    pub fn expr(&self) -> Option<&'static str> {
+        self.source.map(|source| source.expr)
+    }
+
+    /// The path of the module the unwrap is in, if known.
+    pub fn module_path(&self) -> Option<&'static str> {
+        self.source.map(|source| source.module)
+    }
+
+    /// The path of the function the unwrap is in, if known.
+    pub fn function(&self) -> Option<&'static str> {
+        self.source.map(|source| source.function)
+    }
+
+    /// The backtrace of the failure, if one was captured. See
+    /// [`set_backtrace_capture`](crate::set_backtrace_capture).
+    pub fn backtrace(&self) -> Option<&Backtrace> {
+        self.backtrace.as_ref()
+    }
+
+    /// Whether the method that was called can't return, and so takes the
+    /// [`FailureAction`] once the handler returns.
+    pub fn is_fatal(&self) -> bool {
+        self.fatal
+    }
+
+    /// Logs the failure to its [logger](Failure::logger), as the
+    /// [`DefaultHandler`](crate::DefaultHandler) does.
+    ///
+    /// The record's source location is that of the call that failed, and
+    /// the failure's details are attached to it as keys. See the
+    /// [`slog::KV`] implementation.
+    ///
+    /// Like the `slog` macros, nothing is logged if the failure's level is
+    /// disabled at compile time by `slog`'s `max_level_*` features.
+    pub fn log(&self) {
+        if self.level.as_usize() > slog::__slog_static_max_level().as_usize() {
+            return;
+        }
+        let location = slog::RecordLocation {
+            file: self.location.file(),
+            line: self.location.line(),
+            column: self.location.column(),
+            function: self.function().unwrap_or(""),
+            module: self.module_path().unwrap_or(""),
+        };
+        let record_static = slog::RecordStatic {
+            location: &location,
+            tag: "",
+            level: self.level,
+        };
+        self.log_record(&slog::Record::new(
+            &record_static,
+            &format_args!("{}", self.msg),
+            slog::b!(self),
+        ));
+    }
+
+    fn log_record(&self, record: &slog::Record) {
+        let values = slog::OwnedKVList::from(slog::o!());
+
+        // `log` key-values can't pass through a drain, as `slog` keys are
+        // static strings, so they are handed to the `log` facade directly.
+        #[cfg(feature = "log")]
+        if let Some(kv) = self.log_kv {
+            return crate::facade::log(record, &values, kv);
+        }
+        let _ = self.logger().log(record, &values);
+    }
+}
+
+/// Where in the source a failed unwrap is, as recorded by the macros. Not
+/// public API.
+pub struct Source {
+    pub expr: &'static str,
+    pub module: &'static str,
+    pub function: &'static str,
+}
+
+/// The offending value of a failed unwrap, along with how to format it.
+#[derive(Clone, Copy)]
+pub(crate) enum Value<'a> {
+    Debug(&'a dyn fmt::Debug),
+    Display(&'a dyn fmt::Display),
+}
+
+impl fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Debug(value) => write!(f, "{:?}", value),
+            Value::Display(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl fmt::Debug for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Emits the failure's details as the `value`, `method` and `variant` keys,
+/// and the `chain`, `cause.N`, `backtrace`, `expr`, `module` and `function`
+/// keys where applicable, after any key-value pairs passed along with it.
+impl slog::KV for Failure<'_> {
+    fn serialize(
+        &self,
+        record: &slog::Record,
+        serializer: &mut dyn slog::Serializer,
+    ) -> slog::Result {
+        self.kv.serialize(record, serializer)?;
+        #[cfg(feature = "tracing")]
+        if let Some(fields) = self.fields {
+            let mut result = Ok(());
+            fields.visit(&mut |key, value| {
+                if result.is_ok() {
+                    result = serializer.emit_arguments(key, &format_args!("{}", value));
+                }
+            });
+            result?;
+        }
+        if let Some(value) = self.value {
+            serializer.emit_arguments("value", &format_args!("{}", value))?;
+        }
+        if let Some(chain) = self.chain {
+            chain.serialize(serializer)?;
+        }
+        if let Some(backtrace) = &self.backtrace {
+            serializer.emit_arguments("backtrace", &format_args!("{}", backtrace))?;
+        }
+        if let Some(source) = self.source {
+            serializer.emit_str("expr", source.expr)?;
+            serializer.emit_str("module", source.module)?;
+            serializer.emit_str("function", source.function)?;
+        }
+        serializer.emit_str("method", self.method)?;
+        serializer.emit_str("variant", self.variant)
+    }
+}
+
+/// Hands a failed unwrap to the current
+/// [`FailureHandler`](crate::FailureHandler), then panics or takes whichever
+/// other [`FailureAction`] is set.
+#[inline(never)]
+#[cold]
+#[track_caller]
+pub(crate) fn failed(mut failure: Failure) -> ! {
+    failure.fatal = true;
+    failure.backtrace = backtrace::capture();
+    handler::current().handle(&failure);
+    flush::flush();
+
+    match failure_action() {
+        FailureAction::Panic => (),
+        FailureAction::Abort => process::abort(),
+        FailureAction::Exit(code) => process::exit(code),
+    }
+
+    hook::mark_unwrap_failed();
+
+    #[cfg(feature = "panic-quiet")]
+    panic!();
+    #[cfg(not(feature = "panic-quiet"))]
+    match failure.value {
+        Some(value) => panic!("{}: {}", failure.msg, value),
+        None => panic!("{}", failure.msg),
+    }
+}
+
+/// Hands a failed unwrap to the current
+/// [`FailureHandler`](crate::FailureHandler), without panicking.
+#[inline(never)]
+#[cold]
+pub(crate) fn report(failure: Failure) {
+    handler::current().handle(&failure);
+}
@@ -0,0 +1,90 @@
+use std::sync::{mpsc, Arc, RwLock};
+use std::thread;
+use std::time::Duration;
+
+type Flush = Arc<dyn Fn() + Send + Sync>;
+
+static FLUSH_HOOK: RwLock<Option<(Flush, Duration)>> = RwLock::new(None);
+
+/// Registers a hook that flushes pending log records, such as those queued
+/// in an asynchronous drain.
+///
+/// The hook is called after a failed unwrap has been logged and before it
+/// panics, as well as by the [panic hook](crate::install_panic_hook) after
+/// logging a panic. This keeps the record of the failure from being lost
+/// when the process dies, in particular with `panic = "abort"`.
+///
+/// The hook runs on every fatal failure and every panic the hook logs, and
+/// the process often outlives them, e.g. under `catch_unwind` or in another
+/// thread. It should therefore flush without shutting the drain down, for
+/// example by calling [`slog::Logger::flush`] (`slog` 2.8 and later) on a
+/// clone of the logger, for drains that support flushing:
+///
+/// ```
+/// use std::time::Duration;
+///
+/// let log = slog::Logger::root(slog::Discard, slog::o!());
+/// let flushed = log.clone();
+/// slog_unwrap::set_flush_hook(Duration::from_secs(1), move || {
+///     let _ = flushed.flush();
+/// });
+/// ```
+///
+/// Dropping the `AsyncGuard` of [`slog-async`](https://github.com/slog-rs/async)
+/// instead stops its logging thread, so that records logged after the first
+/// call are lost.
+///
+/// The failure path waits for the hook to return for at most `timeout`. To
+/// time it out, each call runs the hook on a newly spawned thread, which is
+/// left running if the timeout expires.
+///
+/// Replaces any previously registered hook. This setting is process-wide.
+pub fn set_flush_hook<F>(timeout: Duration, flush: F)
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    let mut hook = FLUSH_HOOK
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *hook = Some((Arc::new(flush), timeout));
+}
+
+/// Unregisters the hook set by [`set_flush_hook`], if any.
+pub fn clear_flush_hook() {
+    let mut hook = FLUSH_HOOK
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *hook = None;
+}
+
+/// Calls the registered flush hook, if any, waiting for it to return for at
+/// most its timeout.
+pub(crate) fn flush() {
+    let hook = FLUSH_HOOK
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone();
+    let (flush, timeout) = match hook {
+        Some(hook) => hook,
+        None => return,
+    };
+
+    let (done_tx, done_rx) = mpsc::channel();
+    let spawned = thread::Builder::new()
+        .name("slog-unwrap-flush".into())
+        .spawn({
+            let flush = flush.clone();
+            move || {
+                flush();
+                let _ = done_tx.send(());
+            }
+        });
+
+    match spawned {
+        Ok(_) => {
+            let _ = done_rx.recv_timeout(timeout);
+        }
+        // Without a thread to run it on, the hook can't be timed out.
+        Err(_) => flush(),
+    }
+}
@@ -0,0 +1,76 @@
+use crate::global_logger::with_logger;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+/// Extension trait for futures, to carry a logger across `.await` points.
+pub trait WithUnwrapLoggerExt: Future + Sized {
+    /// Wraps the future so that `log` is the logger of failed unwraps while it
+    /// is polled, whichever thread that happens on.
+    ///
+    /// Within each `poll`, `log` is set with [`with_logger`] for the
+    /// [`global::ResultExt`](crate::global::ResultExt) and
+    /// [`global::OptionExt`](crate::global::OptionExt) traits, and, with the
+    /// `scope` feature, as the scoped logger of `slog-scope`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use slog_unwrap::global::OptionExt;
+    /// use slog_unwrap::WithUnwrapLoggerExt;
+    ///
+    /// async fn handle(session: Option<u32>) -> u32 {
+    ///     // Logged to the task's logger, along with its `request` key.
+    ///     session.unwrap_or_default_log()
+    /// }
+    ///
+    /// let log = slog::Logger::root(slog::Discard, slog::o!());
+    /// let task = handle(None).with_unwrap_logger(log.new(slog::o!("request" => 42)));
+    /// ```
+    fn with_unwrap_logger(self, log: slog::Logger) -> WithUnwrapLogger<Self> {
+        WithUnwrapLogger { future: self, log }
+    }
+}
+
+impl<F: Future> WithUnwrapLoggerExt for F {}
+
+/// A future that sets a logger for failed unwraps while polling the future it
+/// wraps. See [`with_unwrap_logger`](WithUnwrapLoggerExt::with_unwrap_logger).
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct WithUnwrapLogger<F> {
+    future: F,
+    log: slog::Logger,
+}
+
+impl<F> WithUnwrapLogger<F> {
+    /// The logger set while the future is polled.
+    pub fn logger(&self) -> &slog::Logger {
+        &self.log
+    }
+
+    /// Unwraps the inner future.
+    pub fn into_inner(self) -> F {
+        self.future
+    }
+}
+
+impl<F: Future> Future for WithUnwrapLogger<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        // SAFETY: `future` is pinned along with the wrapper: it is never moved
+        // out of a pinned wrapper, and the wrapper has no `Drop` impl. `log` is
+        // never pinned.
+        let (future, log) = unsafe {
+            let this = self.get_unchecked_mut();
+            (Pin::new_unchecked(&mut this.future), &this.log)
+        };
+        let poll = || with_logger(log, || future.poll(cx));
+
+        #[cfg(feature = "scope")]
+        return slog_scope::scope(log, poll);
+        #[cfg(not(feature = "scope"))]
+        poll()
+    }
+}
@@ -0,0 +1,37 @@
+//! Extension traits logging to the logger set with
+//! [`set_global_logger`](crate::set_global_logger), or to `stderr` if none is
+//! set.
+//!
+//! The traits have the same methods as the crate's root
+//! [`ResultExt`](crate::ResultExt) and [`OptionExt`](crate::OptionExt), minus
+//! the `log` argument. Import them from this module instead of those:
+//!
+//! ```should_panic
+//! use slog_unwrap::global::OptionExt;
+//!
+//! let log = slog::Logger::root(slog::Discard, slog::o!());
+//! slog_unwrap::set_global_logger(log).unwrap();
+//!
+//! None::<u32>.expect_or_log("no session");
+//! ```
+
+use crate::ext::ext_traits;
+use crate::failure::Target;
+
+ext_traits! {
+    /// Extension trait for Result types, logging to the logger set with
+    /// [`set_global_logger`](crate::set_global_logger), or to `stderr` if none is
+    /// set.
+    pub trait ResultExt;
+
+    /// Extension trait for Option types, logging to the logger set with
+    /// [`set_global_logger`](crate::set_global_logger), or to `stderr` if none is
+    /// set.
+    pub trait OptionExt;
+
+    target: Target::Global,
+    to: "to the global [`slog::Logger`]",
+    level: slog::Level,
+    kv: slog::KV => with_kv,
+    kv_doc: "`kv` is typically built with [`slog::b!`], and only serialized if the unwrap fails.",
+}
@@ -0,0 +1,111 @@
+use crate::kv::KeyValues;
+use std::cell::RefCell;
+use std::fmt::Write as _;
+use std::io::{self, Write as _};
+use std::sync::OnceLock;
+
+static GLOBAL_LOGGER: OnceLock<slog::Logger> = OnceLock::new();
+
+thread_local! {
+    static THREAD_LOGGER: RefCell<Option<slog::Logger>> = const { RefCell::new(None) };
+}
+
+/// Sets the logger that the [`global::ResultExt`](crate::global::ResultExt) and
+/// [`global::OptionExt`](crate::global::OptionExt) traits log failed unwraps to.
+///
+/// The logger can only be set once, and is never dropped. If one is already
+/// set, `log` is returned as an error.
+///
+/// Until a logger is set, failed unwraps are written to `stderr` instead. The
+/// logger can be overridden for a block of code with [`with_logger`].
+pub fn set_global_logger(log: slog::Logger) -> Result<(), slog::Logger> {
+    GLOBAL_LOGGER.set(log)
+}
+
+/// Runs `f` with `log` as the logger of the
+/// [`global::ResultExt`](crate::global::ResultExt) and
+/// [`global::OptionExt`](crate::global::OptionExt) traits on the current thread,
+/// in place of the one set with [`set_global_logger`].
+///
+/// The previous logger is restored when `f` returns or panics, so calls can be
+/// nested. Unlike `slog_scope::scope`, this only affects the current thread,
+/// and doesn't require a process-wide logger to be set.
+///
+/// # Examples
+///
+/// ```should_panic
+/// use slog_unwrap::global::OptionExt;
+///
+/// let log = slog::Logger::root(slog::Discard, slog::o!("request" => 42));
+/// slog_unwrap::with_logger(&log, || {
+///     // Logged to `log`, along with its `request` key.
+///     None::<u32>.expect_or_log("no session");
+/// });
+/// ```
+pub fn with_logger<F, R>(log: &slog::Logger, f: F) -> R
+where
+    F: FnOnce() -> R,
+{
+    let _guard = ThreadLoggerGuard {
+        previous: replace_thread_logger(Some(log.clone())),
+    };
+    f()
+}
+
+fn replace_thread_logger(log: Option<slog::Logger>) -> Option<slog::Logger> {
+    THREAD_LOGGER.with(|current| current.replace(log))
+}
+
+/// Restores the current thread's previous logger when dropped.
+struct ThreadLoggerGuard {
+    previous: Option<slog::Logger>,
+}
+
+impl Drop for ThreadLoggerGuard {
+    fn drop(&mut self) {
+        replace_thread_logger(self.previous.take());
+    }
+}
+
+/// Returns the logger set on the current thread with [`with_logger`], if any.
+pub(crate) fn thread_logger() -> Option<slog::Logger> {
+    THREAD_LOGGER.with(|current| current.borrow().clone())
+}
+
+/// Returns the logger set with [`set_global_logger`], or one that writes to
+/// `stderr` if none is set.
+pub(crate) fn logger() -> &'static slog::Logger {
+    static STDERR_LOGGER: OnceLock<slog::Logger> = OnceLock::new();
+    GLOBAL_LOGGER.get().unwrap_or_else(|| {
+        STDERR_LOGGER.get_or_init(|| slog::Logger::root(StderrDrain, slog::o!()))
+    })
+}
+
+/// A drain that writes each record to `stderr` as a single line, with its
+/// level, location, message and key-value pairs.
+struct StderrDrain;
+
+impl slog::Drain for StderrDrain {
+    type Ok = ();
+    type Err = slog::Never;
+
+    fn log(&self, record: &slog::Record, values: &slog::OwnedKVList) -> Result<(), slog::Never> {
+        let mut line = format!(
+            "{} [{}:{}:{}] {}",
+            record.level().as_short_str(),
+            record.file(),
+            record.line(),
+            record.column(),
+            record.msg()
+        );
+        for (key, value) in KeyValues::of(record, values).iter() {
+            // Can't fail, as writing to a `String` never returns an error.
+            let _ = write!(line, ", {}: {}", key, value);
+        }
+        line.push('\n');
+
+        // There is nowhere left to report a failure to write to `stderr`.
+        let _ = io::stderr().lock().write_all(line.as_bytes());
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use crate::failure::Failure;
+use std::cell::RefCell;
+use std::sync::{Arc, RwLock};
+
+/// Decides what happens when an unwrap fails.
+///
+/// A handler is given each failure before anything else is done about it.
+/// The [`DefaultHandler`] logs it; other handlers can, for example, log it
+/// differently, count it, or send it elsewhere instead.
+///
+/// Once the handler returns from a [fatal](Failure::is_fatal) failure, the
+/// flush hook is called and the [`FailureAction`](crate::FailureAction) is
+/// taken, as they would be with the default handler. A handler can also
+/// panic, abort or exit itself.
+///
+/// Any `Fn(&Failure)` closure that is `Send + Sync` is a handler.
+pub trait FailureHandler: Send + Sync {
+    /// Handles a failed unwrap.
+    fn handle(&self, failure: &Failure<'_>);
+}
+
+impl<F> FailureHandler for F
+where
+    F: Fn(&Failure<'_>) + Send + Sync,
+{
+    fn handle(&self, failure: &Failure<'_>) {
+        self(failure)
+    }
+}
+
+/// The handler used when no other has been set: logs the failure with
+/// [`Failure::log`].
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DefaultHandler;
+
+impl FailureHandler for DefaultHandler {
+    fn handle(&self, failure: &Failure<'_>) {
+        failure.log();
+    }
+}
+
+type Handler = Arc<dyn FailureHandler>;
+
+static FAILURE_HANDLER: RwLock<Option<Handler>> = RwLock::new(None);
+
+thread_local! {
+    static THREAD_FAILURE_HANDLER: RefCell<Option<Handler>> = const { RefCell::new(None) };
+}
+
+/// Sets the handler for failed unwraps.
+///
+/// Replaces any previously set handler. This setting is process-wide, and
+/// is overridden on threads that have their own handler set with
+/// [`set_thread_failure_handler`].
+pub fn set_failure_handler<H>(handler: H)
+where
+    H: FailureHandler + 'static,
+{
+    let mut current = FAILURE_HANDLER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *current = Some(Arc::new(handler));
+}
+
+/// Unsets the handler set by [`set_failure_handler`], if any, going back to
+/// the [`DefaultHandler`].
+pub fn clear_failure_handler() {
+    let mut current = FAILURE_HANDLER
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    *current = None;
+}
+
+/// Sets the handler for failed unwraps on the current thread.
+///
+/// Replaces any handler previously set on this thread, and overrides the one
+/// set with [`set_failure_handler`].
+pub fn set_thread_failure_handler<H>(handler: H)
+where
+    H: FailureHandler + 'static,
+{
+    replace_thread_handler(Some(Arc::new(handler)));
+}
+
+/// Unsets the handler set on the current thread by
+/// [`set_thread_failure_handler`], if any.
+pub fn clear_thread_failure_handler() {
+    replace_thread_handler(None);
+}
+
+/// Sets the current thread's handler, returning the previous one.
+fn replace_thread_handler(handler: Option<Handler>) -> Option<Handler> {
+    THREAD_FAILURE_HANDLER.with(|current| current.replace(handler))
+}
+
+/// Sets the current thread's handler until the returned guard is dropped,
+/// when the previous one is restored.
+#[cfg(feature = "testing")]
+pub(crate) fn scoped_thread_handler(handler: Handler) -> ThreadHandlerGuard {
+    ThreadHandlerGuard {
+        previous: replace_thread_handler(Some(handler)),
+    }
+}
+
+/// Restores the current thread's previous handler when dropped.
+#[cfg(feature = "testing")]
+pub(crate) struct ThreadHandlerGuard {
+    previous: Option<Handler>,
+}
+
+#[cfg(feature = "testing")]
+impl Drop for ThreadHandlerGuard {
+    fn drop(&mut self) {
+        replace_thread_handler(self.previous.take());
+    }
+}
+
+/// Returns the handler in effect on the current thread.
+pub(crate) fn current() -> Handler {
+    if let Some(handler) = THREAD_FAILURE_HANDLER.with(|current| current.borrow().clone()) {
+        return handler;
+    }
+
+    FAILURE_HANDLER
+        .read()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .clone()
+        .unwrap_or_else(|| Arc::new(DefaultHandler))
+}
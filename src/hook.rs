@@ -0,0 +1,103 @@
+use crate::flush;
+use std::cell::Cell;
+use std::panic::{self, PanicHookInfo};
+use std::sync::{Arc, Once, RwLock};
+use std::thread;
+
+type LogPanic = Arc<dyn Fn(&PanicHookInfo) + Send + Sync>;
+
+/// Installs the panic hook, once.
+static INSTALL_HOOK: Once = Once::new();
+
+/// How the panic hook logs panics, replaced by each call that installs it.
+static LOG_PANIC: RwLock<Option<LogPanic>> = RwLock::new(None);
+
+thread_local! {
+    /// Set when a failed unwrap is about to panic, so that the panic hook
+    /// does not log it a second time.
+    static UNWRAP_FAILED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Marks the upcoming panic on this thread as coming from a failed unwrap,
+/// which has already been logged.
+pub(crate) fn mark_unwrap_failed() {
+    if INSTALL_HOOK.is_completed() {
+        UNWRAP_FAILED.with(|flag| flag.set(true));
+    }
+}
+
+/// Installs a panic hook that logs every panic to `log` at a [`Critical`]
+/// level, with its payload as the message and its location and thread as
+/// the `location` and `thread` keys.
+///
+/// Panics caused by this crate's failed unwraps have already been logged
+/// and are not logged again. The previously installed panic hook is still
+/// called after logging, so panics keep being printed to `stderr` as well.
+///
+/// The hook is only installed once. Later calls, including to
+/// `install_scoped_panic_hook`, replace the logger it logs to instead of
+/// installing another hook.
+///
+/// [`Critical`]: slog::Level::Critical
+pub fn install_panic_hook<D>(log: slog::Logger<D>)
+where
+    D: slog::SendSyncUnwindSafeDrain<Ok = (), Err = slog::Never> + 'static,
+{
+    install(move |info| log_panic(&log, info));
+}
+
+/// Installs a panic hook that logs every panic to the logger set in
+/// [`slog-scope`](https://github.com/slog-rs/scope) at the time of the panic.
+///
+/// See [`install_panic_hook`].
+#[cfg(feature = "scope")]
+pub fn install_scoped_panic_hook() {
+    install(|info| log_panic(&slog_scope::logger(), info));
+}
+
+fn install<F>(log_panic: F)
+where
+    F: Fn(&PanicHookInfo) + Send + Sync + 'static,
+{
+    *LOG_PANIC
+        .write()
+        .unwrap_or_else(|poisoned| poisoned.into_inner()) = Some(Arc::new(log_panic));
+
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !UNWRAP_FAILED.with(|flag| flag.replace(false)) {
+                let log_panic = LOG_PANIC
+                    .read()
+                    .unwrap_or_else(|poisoned| poisoned.into_inner())
+                    .clone();
+                if let Some(log_panic) = log_panic {
+                    log_panic(info);
+                }
+                flush::flush();
+            }
+            previous(info);
+        }));
+    });
+}
+
+fn log_panic<D>(log: &slog::Logger<D>, info: &PanicHookInfo)
+where
+    D: slog::SendSyncUnwindSafeDrain<Ok = (), Err = slog::Never>,
+{
+    let payload = info.payload();
+    let payload = payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("Box<dyn Any>");
+    let thread = thread::current();
+    let location = info.location();
+
+    slog::crit!(
+        log,
+        "{}", payload;
+        "location" => location.map(|location| location.to_string()),
+        "thread" => thread.name().unwrap_or("<unnamed>"),
+    );
+}
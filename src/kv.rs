@@ -0,0 +1,56 @@
+use std::fmt;
+
+/// The key-value pairs of a record, formatted as strings, in the order they
+/// were serialized.
+#[derive(Default)]
+pub(crate) struct KeyValues(pub(crate) Vec<(String, String)>);
+
+impl KeyValues {
+    /// Collects the key-value pairs of `record`, followed by those of its
+    /// logger.
+    pub(crate) fn of(record: &slog::Record, values: &slog::OwnedKVList) -> Self {
+        let mut kv = Self::default();
+        kv.push_record(record, values);
+        kv
+    }
+
+    /// Appends the key-value pairs of `record`, followed by those of its
+    /// logger.
+    pub(crate) fn push_record(&mut self, record: &slog::Record, values: &slog::OwnedKVList) {
+        // Neither can fail, as `KeyValues` never returns an error.
+        let _ = slog::KV::serialize(&record.kv(), record, self);
+        let _ = slog::KV::serialize(values, record, self);
+    }
+
+    /// Appends the key-values of a `log` source.
+    #[cfg(feature = "log")]
+    pub(crate) fn push_source(&mut self, kv: &dyn log::kv::Source) {
+        // Can't fail, as `KeyValues` never returns an error.
+        let _ = kv.visit(self);
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+}
+
+impl slog::Serializer for KeyValues {
+    fn emit_arguments(&mut self, key: slog::Key, val: &fmt::Arguments) -> slog::Result {
+        self.0.push((key.to_string(), val.to_string()));
+        Ok(())
+    }
+}
+
+#[cfg(feature = "log")]
+impl<'kvs> log::kv::VisitSource<'kvs> for KeyValues {
+    fn visit_pair(
+        &mut self,
+        key: log::kv::Key<'kvs>,
+        value: log::kv::Value<'kvs>,
+    ) -> Result<(), log::kv::Error> {
+        self.0.push((key.to_string(), value.to_string()));
+        Ok(())
+    }
+}
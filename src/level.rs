@@ -0,0 +1,54 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// The process-wide default level, as given by [`slog::Level::as_usize`].
+/// Zero means it has not been set.
+static DEFAULT_LEVEL: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets the level at which failed unwraps are logged when no level is given
+/// explicitly, i.e. by every method without an `_at` suffix.
+///
+/// This setting is process-wide. The default is [`Critical`].
+///
+/// [`Critical`]: slog::Level::Critical
+pub fn set_default_level(level: slog::Level) {
+    DEFAULT_LEVEL.store(level.as_usize(), Ordering::Relaxed);
+}
+
+/// Returns the level at which failed unwraps are logged when no level is
+/// given explicitly. See [`set_default_level`].
+pub fn default_level() -> slog::Level {
+    slog::Level::from_usize(DEFAULT_LEVEL.load(Ordering::Relaxed)).unwrap_or(slog::Level::Critical)
+}
+
+/// The process-wide fallback level, as given by [`slog::Level::as_usize`].
+/// Zero means it has not been set.
+static FALLBACK_LEVEL: AtomicUsize = AtomicUsize::new(0);
+
+/// Sets the level at which failed unwraps are logged by the methods that
+/// return a fallback value instead of panicking, such as
+/// `unwrap_or_default_log`.
+///
+/// This setting is process-wide. The default is [`Warning`].
+///
+/// [`Warning`]: slog::Level::Warning
+pub fn set_fallback_level(level: slog::Level) {
+    FALLBACK_LEVEL.store(level.as_usize(), Ordering::Relaxed);
+}
+
+/// Returns the level at which failed unwraps that return a fallback value
+/// are logged. See [`set_fallback_level`].
+pub fn fallback_level() -> slog::Level {
+    slog::Level::from_usize(FALLBACK_LEVEL.load(Ordering::Relaxed)).unwrap_or(slog::Level::Warning)
+}
+
+/// Converts the level taken by the `_at` methods of the logger-free traits
+/// into the [`slog::Level`] a failure is logged at.
+pub(crate) trait IntoLevel {
+    fn into_level(self) -> slog::Level;
+}
+
+impl IntoLevel for slog::Level {
+    fn into_level(self) -> slog::Level {
+        self
+    }
+}
@@ -1,11 +1,11 @@
 //! This crate provides `.unwrap_or_log()` and `.expect_or_log()` methods on `Result` and `Option` types that log failed unwraps to a [`slog::Logger`]. This is useful when, for example, you have a [syslog](https://github.com/slog-rs/syslog) drain or a database drain, and you want your unwrap failures to show up there instead of being printed to `stderr`.
 //!
-//! Its API aims to mirror Rust's `std` — see all the [supported methods](#methods) below. Failed unwraps are logged at a level of [`Critical`].
+//! Its API aims to mirror Rust's `std` — see all the [supported methods](#methods) below. Failed unwraps are logged at a level of [`Critical`] by default, with the offending value, the method that was called, and the variant that was found attached to the record as the `value`, `method`, and `variant` keys. The record's source location is that of the failed call, not of this crate.
 //!
 //! ### Usage
 //! Add the following to your `Cargo.toml`:
 //! ```toml
-//! slog-unwrap = "0.9"
+//! slog-unwrap = "0.10"
 //! ```
 //!
 //! Next, bring the [`ResultExt`] and/or [`OptionExt`] traits into scope, and make use of the new logging methods.
@@ -32,14 +32,58 @@
 //! | [`Option::expect_none(msg)`]<sup>†</sup>   | [`Option::expect_none_or_log(&log, msg)`] | [`OptionExt`] |
 //!
 //! *†: unstable in `std`*<br/>
-//! *Note: enabling the `scope` feature drops the `&log` argument from all methods.*
+//! *Note: the `scope` feature adds the [`scope::ResultExt`] and [`scope::OptionExt`] traits, whose methods drop the `&log` argument.*
+//!
+//! Without depending on `slog-scope`, a logger can also be set once for the whole process with [`set_global_logger`], and used by the [`global::ResultExt`] and [`global::OptionExt`] traits, whose methods also drop the `&log` argument. Until a logger is set, these write failed unwraps to `stderr`. [`with_logger`] overrides the logger on the current thread for the duration of a closure, restoring the previous one when it returns or panics.
+//!
+//! Thread-local loggers, including those of `slog-scope`, don't follow async tasks as they move between threads. To log a task's failed unwraps to its own logger, wrap it with [`with_unwrap_logger(log)`], which sets `log` for each `poll` of the task, both for [`global::ResultExt`] and [`global::OptionExt`] and, with the `scope` feature, for [`scope::ResultExt`] and [`scope::OptionExt`].
+//!
+//! The `&log` argument can be a `slog::Logger` of any drain type, `Logger<D>`. Loggers built with `Logger::root_typed` keep their concrete drain type, and avoid the dynamic dispatch of the default `Logger`. More generally, the argument can be anything implementing [`AsLogger`]: a logger, a reference to one, an `Arc<Logger>`, or a type of your own that owns a logger, so that a service can pass `self`.
+//!
+//! Every method also has an `_at` form taking a [`slog::Level`] (e.g. `expect_or_log_at(&log, Level::Error, msg)`). The level used by the other methods can be changed process-wide with [`set_default_level`].
+//!
+//! For errors implementing [`std::error::Error`], [`Result::unwrap_or_log_chain(&log)`] and [`Result::expect_or_log_chain(&log, msg)`] also log the error's whole `source()` chain, both as a combined `chain` key and as one `cause.N` key per cause. The `cause.N` keys are numbered from the first `source()`, as in the multi-line form, and stop at `cause.15`; later causes are only part of `chain`. The combined form is compact (`a: b: c`) by default, and can be switched to a multi-line form with [`set_chain_style`].
+//!
+//! For values whose [`Display`] output is more useful than their `Debug` output, `unwrap_or_log_display`, `expect_or_log_display`, `unwrap_none_or_log_display` and `expect_none_or_log_display` format the offending value with [`Display`] instead.
+//!
+//! To avoid building `expect` messages on the success path, `expect_or_log_with(&log, || format!(...))` only calls its closure if the unwrap fails, and the [`expect_or_log!`] macro takes [`format_args!`]-style arguments that are only evaluated and formatted on failure: `expect_or_log!(result, &log, "request {} failed", id)`.
+//!
+//! Context about a failure, such as a user id or a request path, can be attached to its record without building a child logger. The [`unwrap_or_log!`] and [`expect_or_log!`] macros take key-value pairs after a `;`, in the syntax of `slog::b!`, and only evaluate them on failure: `unwrap_or_log!(result, &log; "user" => id, "path" => %path.display())`. The `unwrap_or_log_kv` and `expect_or_log_kv` methods take the pairs as a `slog::BorrowedKV` or `slog::OwnedKV` instead.
+//!
+//! Like `assert!`, both macros also record the source text of the expression being unwrapped, and the module and function they are called from, as the `expr`, `module` and `function` keys.
+//!
+//! To log a failure and carry on instead of panicking, use [`Result::ok_or_log(&log)`], which converts to an `Option`, or [`Result::log_err(&log, level)`] and [`Option::log_none(&log, level)`], which pass the value through unchanged. [`Option::none_or_log(&log)`] is the non-panicking form of `unwrap_none_or_log`.
+//!
+//! Mirroring `unwrap_or_default()`, `unwrap_or_else()` and `unwrap_or()` from `std`, the `unwrap_or_default_log(&log)`, `unwrap_or_log_else(&log, op)` and `unwrap_or_log_value(&log, fallback)` methods log the failure and return a fallback value instead of panicking. They log at a level of [`Warning`] by default, which can be changed process-wide with [`set_fallback_level`].
+//!
+//! Failed unwraps that panic can also log a backtrace under the `backtrace` key. By default one is captured only when `RUST_BACKTRACE` enables it; this can be changed with [`set_backtrace_capture`] or the `backtrace` feature.
+//!
+//! Panics that don't come from this crate, such as out-of-bounds indexing or panics in third-party code, can be sent to a logger as well by installing a panic hook with [`install_panic_hook`]. Failed unwraps are recognized by the hook and not logged twice.
+//!
+//! When using an asynchronous drain, register a flush hook with [`set_flush_hook`] so that the record of a failed unwrap is written out before the process unwinds or aborts.
+//!
+//! Instead of panicking, failed unwraps can abort the process or exit it with a given code. This is selected at runtime with [`set_failure_action`], or at compile time with the `abort` and `exit` features.
+//!
+//! What happens to a failed unwrap can be customized with a [`FailureHandler`], set process-wide with [`set_failure_handler`] or for the current thread with [`set_thread_failure_handler`]. The handler is given a [`Failure`] describing the message, the offending value, the caller's location, the method that was called and the logger. The [`DefaultHandler`] logs the failure; for failures that panic, the flush hook and the failure action still apply once the handler returns.
 //!
 //!
 //! ### Features
 //! * **`panic-quiet`**: causes failed unwraps to panic with an empty message.<br/>
 //!   This feature is enabled by default — if you'd like the unwrap error message to also show in the panic message, disable default features in your `Cargo.toml` as follows:<br/>
-//!   `slog-unwrap = { version = "0.9", default-features = false }`
-//! * **`scope`**: adds support for [`slog-scope`](https://github.com/slog-rs/scope), which removes the need to pass a [`slog::Logger`] to the various methods.
+//!   `slog-unwrap = { version = "0.10", default-features = false }`
+//! * **`scope`**: adds support for [`slog-scope`](https://github.com/slog-rs/scope), which removes the need to pass a [`slog::Logger`] to the various methods.<br/>
+//!   This feature is additive: it brings in the [`scope::ResultExt`] and [`scope::OptionExt`] traits, and leaves [`ResultExt`] and [`OptionExt`] available alongside them.
+//! * **`log`**: adds the [`log_facade::ResultExt`] and [`log_facade::OptionExt`] traits, with the same methods minus the `&log` argument, which log failed unwraps through the [`log`](https://github.com/rust-lang/log) facade instead of a [`slog::Logger`]. Their `_at` methods take a `log::Level`, and their `_kv` methods any `log::kv::Source`, so that `slog` isn't needed as a direct dependency. Key-value pairs are passed along as `log` key-values.
+//! * **`tracing`**: adds the [`tracing_events::ResultExt`] and [`tracing_events::OptionExt`] traits, with the same methods minus the `&log` argument, which log failed unwraps as [`tracing`](https://github.com/tokio-rs/tracing) events within the current span. Their `_at` methods take a `tracing::Level`, and their `_kv` methods `(key, value)` pairs whose values implement `Display`, so that `slog` isn't needed as a direct dependency. The message, value, caller location and method name are recorded as the event's fields, and the `_kv` pairs together as its `kv` field. Events have the `slog_unwrap` target, so filters must name it, e.g. `slog_unwrap=error`, rather than the calling crate.
+//! * **`backtrace`**: always captures a backtrace of failed unwraps that panic and logs it along with them, regardless of `RUST_BACKTRACE`.
+//! * **`abort`**: makes failed unwraps abort the process instead of panicking.
+//! * **`exit`**: makes failed unwraps exit the process with code 1 instead of panicking. Ignored if `abort` is also enabled.
+//! * **`testing`**: adds the [`testing`] module, with a capturing drain, the [`assert_logged_failure!`] macro and an intercept mode for testing failed unwraps.
+//!
+//!
+//! ### Upgrading from 0.9
+//! * With the `scope` feature, the root [`ResultExt`] and [`OptionExt`] traits keep their `&log` argument. Code that called them without a logger should import the [`scope::ResultExt`] and [`scope::OptionExt`] traits instead, e.g. `use slog_unwrap::scope::{OptionExt, ResultExt};` in place of `use slog_unwrap::{OptionExt, ResultExt};`.
+//! * The methods of [`ResultExt`] and [`OptionExt`] are generic over the logger, which can be anything implementing [`AsLogger`]. Calls passing a `&slog::Logger` are unchanged, but turbofish calls and function pointers to the methods need the logger's type as their first type parameter.
 //!
 //!
 //! ### Alternatives
@@ -48,7 +92,11 @@
 //! [`slog::Logger`]: https://docs.rs/slog/*/slog/struct.Logger.html
 //! [`ResultExt`]: https://docs.rs/slog-unwrap/*/slog_unwrap/trait.ResultExt.html
 //! [`OptionExt`]: https://docs.rs/slog-unwrap/*/slog_unwrap/trait.OptionExt.html
+//! [`scope::ResultExt`]: https://docs.rs/slog-unwrap/*/slog_unwrap/scope/trait.ResultExt.html
+//! [`scope::OptionExt`]: https://docs.rs/slog-unwrap/*/slog_unwrap/scope/trait.OptionExt.html
 //! [`Critical`]: https://docs.rs/slog/*/slog/enum.Level.html#variant.Critical
+//! [`slog::Level`]: https://docs.rs/slog/*/slog/enum.Level.html
+//! [`set_default_level`]: https://docs.rs/slog-unwrap/*/slog_unwrap/fn.set_default_level.html
 //! [`Result::unwrap()`]: https://doc.rust-lang.org/std/result/enum.Result.html#method.unwrap
 //! [`Result::expect(msg)`]: https://doc.rust-lang.org/std/result/enum.Result.html#method.expect
 //! [`Result::unwrap_err()`]: https://doc.rust-lang.org/std/result/enum.Result.html#method.unwrap_err
@@ -65,13 +113,106 @@
 //! [`Option::expect_or_log(&log, msg)`]: https://docs.rs/slog-unwrap/*/slog_unwrap/trait.OptionExt.html#tymethod.expect_or_log
 //! [`Option::unwrap_none_or_log(&log)`]: https://docs.rs/slog-unwrap/*/slog_unwrap/trait.OptionExt.html#tymethod.unwrap_none_or_log
 //! [`Option::expect_none_or_log(&log, msg)`]: https://docs.rs/slog-unwrap/*/slog_unwrap/trait.OptionExt.html#tymethod.expect_none_or_log
+//! [`std::error::Error`]: https://doc.rust-lang.org/std/error/trait.Error.html
+//! [`set_chain_style`]: https://docs.rs/slog-unwrap/*/slog_unwrap/fn.set_chain_style.html
+//! [`Result::unwrap_or_log_chain(&log)`]: https://docs.rs/slog-unwrap/*/slog_unwrap/trait.ResultExt.html#tymethod.unwrap_or_log_chain
+//! [`Result::expect_or_log_chain(&log, msg)`]: https://docs.rs/slog-unwrap/*/slog_unwrap/trait.ResultExt.html#tymethod.expect_or_log_chain
+//! [`Display`]: https://doc.rust-lang.org/std/fmt/trait.Display.html
+//! [`expect_or_log!`]: https://docs.rs/slog-unwrap/*/slog_unwrap/macro.expect_or_log.html
+//! [`format_args!`]: https://doc.rust-lang.org/std/macro.format_args.html
+//! [`Result::ok_or_log(&log)`]: https://docs.rs/slog-unwrap/*/slog_unwrap/trait.ResultExt.html#tymethod.ok_or_log
+//! [`Result::log_err(&log, level)`]: https://docs.rs/slog-unwrap/*/slog_unwrap/trait.ResultExt.html#tymethod.log_err
+//! [`Option::log_none(&log, level)`]: https://docs.rs/slog-unwrap/*/slog_unwrap/trait.OptionExt.html#tymethod.log_none
+//! [`Option::none_or_log(&log)`]: https://docs.rs/slog-unwrap/*/slog_unwrap/trait.OptionExt.html#tymethod.none_or_log
+//! [`Warning`]: https://docs.rs/slog/*/slog/enum.Level.html#variant.Warning
+//! [`set_fallback_level`]: https://docs.rs/slog-unwrap/*/slog_unwrap/fn.set_fallback_level.html
+//! [`set_backtrace_capture`]: https://docs.rs/slog-unwrap/*/slog_unwrap/fn.set_backtrace_capture.html
+//! [`install_panic_hook`]: https://docs.rs/slog-unwrap/*/slog_unwrap/fn.install_panic_hook.html
+//! [`set_flush_hook`]: https://docs.rs/slog-unwrap/*/slog_unwrap/fn.set_flush_hook.html
+//! [`set_failure_action`]: https://docs.rs/slog-unwrap/*/slog_unwrap/fn.set_failure_action.html
+//! [`FailureHandler`]: https://docs.rs/slog-unwrap/*/slog_unwrap/trait.FailureHandler.html
+//! [`set_failure_handler`]: https://docs.rs/slog-unwrap/*/slog_unwrap/fn.set_failure_handler.html
+//! [`set_thread_failure_handler`]: https://docs.rs/slog-unwrap/*/slog_unwrap/fn.set_thread_failure_handler.html
+//! [`Failure`]: https://docs.rs/slog-unwrap/*/slog_unwrap/struct.Failure.html
+//! [`DefaultHandler`]: https://docs.rs/slog-unwrap/*/slog_unwrap/struct.DefaultHandler.html
+//! [`testing`]: https://docs.rs/slog-unwrap/*/slog_unwrap/testing/index.html
+//! [`assert_logged_failure!`]: https://docs.rs/slog-unwrap/*/slog_unwrap/macro.assert_logged_failure.html
+//! [`unwrap_or_log!`]: https://docs.rs/slog-unwrap/*/slog_unwrap/macro.unwrap_or_log.html
+//! [`log_facade::ResultExt`]: https://docs.rs/slog-unwrap/*/slog_unwrap/log_facade/trait.ResultExt.html
+//! [`log_facade::OptionExt`]: https://docs.rs/slog-unwrap/*/slog_unwrap/log_facade/trait.OptionExt.html
+//! [`tracing_events::ResultExt`]: https://docs.rs/slog-unwrap/*/slog_unwrap/tracing_events/trait.ResultExt.html
+//! [`tracing_events::OptionExt`]: https://docs.rs/slog-unwrap/*/slog_unwrap/tracing_events/trait.OptionExt.html
+//! [`AsLogger`]: https://docs.rs/slog-unwrap/*/slog_unwrap/trait.AsLogger.html
+//! [`set_global_logger`]: https://docs.rs/slog-unwrap/*/slog_unwrap/fn.set_global_logger.html
+//! [`global::ResultExt`]: https://docs.rs/slog-unwrap/*/slog_unwrap/global/trait.ResultExt.html
+//! [`global::OptionExt`]: https://docs.rs/slog-unwrap/*/slog_unwrap/global/trait.OptionExt.html
+//! [`with_logger`]: https://docs.rs/slog-unwrap/*/slog_unwrap/fn.with_logger.html
+//! [`with_unwrap_logger(log)`]: https://docs.rs/slog-unwrap/*/slog_unwrap/trait.WithUnwrapLoggerExt.html#method.with_unwrap_logger
+
+mod action;
+pub use crate::action::{failure_action, set_failure_action, FailureAction};
+
+mod backtrace;
+pub use crate::backtrace::{backtrace_capture, set_backtrace_capture, BacktraceCapture};
+
+mod chain;
+pub use crate::chain::{chain_style, set_chain_style, ChainStyle};
+
+#[cfg(feature = "tracing")]
+mod event;
+
+#[cfg(feature = "log")]
+mod facade;
+
+mod ext;
+
+mod failure;
+pub use crate::failure::{Failure, LoggerDrain};
+
+mod flush;
+pub use crate::flush::{clear_flush_hook, set_flush_hook};
+
+mod future;
+pub use crate::future::{WithUnwrapLogger, WithUnwrapLoggerExt};
+
+pub mod global;
+mod global_logger;
+pub use crate::global_logger::{set_global_logger, with_logger};
+
+mod handler;
+pub use crate::handler::{
+    clear_failure_handler, clear_thread_failure_handler, set_failure_handler,
+    set_thread_failure_handler, DefaultHandler, FailureHandler,
+};
+
+mod hook;
+pub use crate::hook::install_panic_hook;
+#[cfg(feature = "scope")]
+pub use crate::hook::install_scoped_panic_hook;
+
+mod kv;
+
+mod level;
+pub use crate::level::{default_level, fallback_level, set_default_level, set_fallback_level};
+
+mod logger;
+pub use crate::logger::AsLogger;
+
+mod macros;
+#[doc(hidden)]
+pub use crate::macros::__private;
 
-#[cfg(not(feature = "scope"))]
 mod slog;
-#[cfg(not(feature = "scope"))]
 pub use crate::slog::*;
 
 #[cfg(feature = "scope")]
-mod scope;
-#[cfg(feature = "scope")]
-pub use crate::scope::*;
+pub mod scope;
+
+#[cfg(feature = "log")]
+pub mod log_facade;
+
+#[cfg(feature = "tracing")]
+pub mod tracing_events;
+
+#[cfg(feature = "testing")]
+pub mod testing;
@@ -0,0 +1,30 @@
+//! Extension traits logging through the [`log`](https://docs.rs/log) facade.
+//!
+//! The traits have the same methods as the crate's root
+//! [`ResultExt`](crate::ResultExt) and [`OptionExt`](crate::OptionExt), minus
+//! the `log` argument, and take a `log::Level` and `log::kv::Source` in place
+//! of their `slog` counterparts. Import them from this module instead of
+//! those:
+//!
+//! ```should_panic
+//! use slog_unwrap::log_facade::OptionExt;
+//!
+//! None::<u32>.expect_or_log_kv("no session", [("user", 42)]);
+//! ```
+
+use crate::ext::ext_traits;
+use crate::failure::Target;
+
+ext_traits! {
+    /// Extension trait for Result types, logging through the `log` facade.
+    pub trait ResultExt;
+
+    /// Extension trait for Option types, logging through the `log` facade.
+    pub trait OptionExt;
+
+    target: Target::Log,
+    to: "to the `log` facade",
+    level: log::Level,
+    kv: log::kv::Source => with_log_kv,
+    kv_doc: "`kv` can be any [`log::kv::Source`], such as an array of `(key, value)` pairs, and is only read if the unwrap fails.",
+}
@@ -0,0 +1,98 @@
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Types that a [`slog::Logger`] can be borrowed from, to log failed unwraps
+/// to.
+///
+/// The methods of [`ResultExt`](crate::ResultExt) and
+/// [`OptionExt`](crate::OptionExt) take any `AsLogger`. It is implemented for
+/// loggers of any drain type, and for references and smart pointers to an
+/// `AsLogger`. Implementing it for types that own a logger lets them be
+/// passed in place of it.
+///
+/// # Examples
+///
+/// ```should_panic
+/// use slog_unwrap::{AsLogger, OptionExt};
+///
+/// struct Service {
+///     log: slog::Logger,
+/// }
+///
+/// impl AsLogger for Service {
+///     type Drain = <slog::Logger as AsLogger>::Drain;
+///
+///     fn as_logger(&self) -> &slog::Logger {
+///         &self.log
+///     }
+/// }
+///
+/// impl Service {
+///     fn handle(&self, session: Option<u32>) -> u32 {
+///         session.expect_or_log(self, "no session")
+///     }
+/// }
+///
+/// let service = Service {
+///     log: slog::Logger::root(slog::Discard, slog::o!()),
+/// };
+/// service.handle(None);
+/// ```
+pub trait AsLogger {
+    /// The drain type of the logger.
+    type Drain: slog::SendSyncUnwindSafeDrain<Ok = (), Err = slog::Never>;
+
+    /// Borrows the logger.
+    fn as_logger(&self) -> &slog::Logger<Self::Drain>;
+}
+
+impl<D> AsLogger for slog::Logger<D>
+where
+    D: slog::SendSyncUnwindSafeDrain<Ok = (), Err = slog::Never>,
+{
+    type Drain = D;
+
+    fn as_logger(&self) -> &slog::Logger<D> {
+        self
+    }
+}
+
+impl<L: AsLogger + ?Sized> AsLogger for &L {
+    type Drain = L::Drain;
+
+    fn as_logger(&self) -> &slog::Logger<L::Drain> {
+        (**self).as_logger()
+    }
+}
+
+impl<L: AsLogger + ?Sized> AsLogger for &mut L {
+    type Drain = L::Drain;
+
+    fn as_logger(&self) -> &slog::Logger<L::Drain> {
+        (**self).as_logger()
+    }
+}
+
+impl<L: AsLogger + ?Sized> AsLogger for Box<L> {
+    type Drain = L::Drain;
+
+    fn as_logger(&self) -> &slog::Logger<L::Drain> {
+        (**self).as_logger()
+    }
+}
+
+impl<L: AsLogger + ?Sized> AsLogger for Rc<L> {
+    type Drain = L::Drain;
+
+    fn as_logger(&self) -> &slog::Logger<L::Drain> {
+        (**self).as_logger()
+    }
+}
+
+impl<L: AsLogger + ?Sized> AsLogger for Arc<L> {
+    type Drain = L::Drain;
+
+    fn as_logger(&self) -> &slog::Logger<L::Drain> {
+        (**self).as_logger()
+    }
+}
@@ -0,0 +1,318 @@
+/// Unwraps a `Result` or an `Option`, yielding the content of an `Ok` or a
+/// `Some`.
+///
+/// This is [`ResultExt::unwrap_or_log`](crate::ResultExt::unwrap_or_log) and
+/// [`OptionExt::unwrap_or_log`](crate::OptionExt::unwrap_or_log), with
+/// optional key-value pairs describing the context of the failure following
+/// a `;`, in the syntax of [`slog::b!`]. The pairs are only evaluated if the
+/// unwrap fails, and are only added to its record.
+///
+/// A message in [`format_args!`]-style can be given as well, making this
+/// the same as [`expect_or_log!`](crate::expect_or_log).
+///
+/// Like [`assert!`], the macro records the source text of the expression
+/// being unwrapped. It is logged as the `expr` key, along with the paths of
+/// the enclosing module and function as the `module` and `function` keys.
+///
+/// # Panics
+///
+/// Panics if the value is an `Err` or a `None`, logging an error message
+/// (and the content of the `Err`, if any) to a [`slog::Logger`] at the
+/// [default level].
+///
+/// [default level]: crate::set_default_level
+///
+/// # Examples
+///
+/// ```should_panic
+/// let logger = slog::Logger::root(slog::Discard, slog::o!());
+/// let user_id = 42;
+/// let path = std::path::Path::new("/etc/app.toml");
+/// let not_great: Result<(), _> = Result::Err("not terrible");
+///
+/// slog_unwrap::unwrap_or_log!(not_great, &logger; "user" => user_id, "path" => %path.display());
+/// ```
+#[macro_export]
+macro_rules! unwrap_or_log {
+    ($value:expr, $log:expr $(,)?) => {
+        match $crate::__private::IntoResult::into_result($value) {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(failed) => $crate::__private::Failed::unwrap_failed(
+                failed,
+                $log,
+                &$crate::__source!($value),
+                &(),
+            ),
+        }
+    };
+    ($value:expr, $log:expr; $($kv:tt)+) => {
+        match $crate::__private::IntoResult::into_result($value) {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(failed) => $crate::__private::Failed::unwrap_failed(
+                failed,
+                $log,
+                &$crate::__source!($value),
+                &$crate::__private::slog::kv!($($kv)+),
+            ),
+        }
+    };
+    ($value:expr, $log:expr, $($arg:tt)+) => {
+        $crate::expect_or_log!($value, $log, $($arg)+)
+    };
+}
+
+/// Unwraps a `Result` or an `Option`, yielding the content of an `Ok` or a
+/// `Some`.
+///
+/// This is [`ResultExt::expect_or_log`](crate::ResultExt::expect_or_log) and
+/// [`OptionExt::expect_or_log`](crate::OptionExt::expect_or_log) with a
+/// message taking [`format_args!`]-style arguments. The message is only
+/// formatted, and its arguments only evaluated, if the unwrap fails.
+///
+/// Key-value pairs describing the context of the failure can follow the
+/// message after a `;`, in the syntax of [`slog::b!`]. Like the message,
+/// they are only evaluated if the unwrap fails, and are only added to its
+/// record.
+///
+/// Like [`assert!`], the macro records the source text of the expression
+/// being unwrapped. It is logged as the `expr` key, along with the paths of
+/// the enclosing module and function as the `module` and `function` keys.
+///
+/// # Panics
+///
+/// Panics if the value is an `Err` or a `None`, logging the formatted
+/// message (and the content of the `Err`, if any) to a [`slog::Logger`] at
+/// the [default level].
+///
+/// [default level]: crate::set_default_level
+///
+/// # Examples
+///
+/// ```should_panic
+/// let logger = slog::Logger::root(slog::Discard, slog::o!());
+/// let request_id = 42;
+/// let not_great: Result<(), _> = Result::Err("not terrible");
+///
+/// slog_unwrap::expect_or_log!(not_great, &logger, "request {} failed", request_id);
+/// ```
+///
+/// ```should_panic
+/// let logger = slog::Logger::root(slog::Discard, slog::o!());
+/// let user_id = 42;
+/// let nothing: Option<()> = None;
+///
+/// slog_unwrap::expect_or_log!(nothing, &logger, "no session"; "user" => user_id);
+/// ```
+#[macro_export]
+macro_rules! expect_or_log {
+    (@split [$value:expr, $log:expr] [$($arg:tt)+] ; $($kv:tt)+) => {
+        match $crate::__private::IntoResult::into_result($value) {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(failed) => $crate::__private::Failed::expect_failed(
+                failed,
+                $log,
+                &$crate::__source!($value),
+                &::core::format_args!($($arg)+),
+                &$crate::__private::slog::kv!($($kv)+),
+            ),
+        }
+    };
+    (@split [$value:expr, $log:expr] [$($arg:tt)+]) => {
+        match $crate::__private::IntoResult::into_result($value) {
+            ::core::result::Result::Ok(value) => value,
+            ::core::result::Result::Err(failed) => $crate::__private::Failed::expect_failed(
+                failed,
+                $log,
+                &$crate::__source!($value),
+                &::core::format_args!($($arg)+),
+                &(),
+            ),
+        }
+    };
+    (@split [$value:expr, $log:expr] [$($arg:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::expect_or_log!(@split [$value, $log] [$($arg)* $next] $($rest)*)
+    };
+    ($value:expr, $log:expr, $($arg:tt)+) => {
+        $crate::expect_or_log!(@split [$value, $log] [] $($arg)+)
+    };
+}
+
+/// Records the source text of `$value`, along with the module and function
+/// the macro is expanded in. Not public API.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __source {
+    ($value:expr) => {
+        $crate::__private::Source {
+            expr: ::core::stringify!($value),
+            module: ::core::module_path!(),
+            function: {
+                fn f() {}
+                $crate::__private::function_name(::core::any::type_name_of_val(&f))
+            },
+        }
+    };
+}
+
+/// Support code for the exported macros. Not public API.
+pub mod __private {
+    pub use crate::failure::Source;
+    use crate::failure::{failed, Failure, Target};
+    use crate::level::default_level;
+    use crate::logger::AsLogger;
+    use std::fmt;
+
+    pub use slog;
+
+    /// Turns the type name of a function `f` defined in the body of another
+    /// into the path of the latter, leaving out any closures it is in.
+    pub fn function_name(name: &'static str) -> &'static str {
+        let name = name.strip_suffix("::f").unwrap_or(name);
+        name.trim_end_matches("::{{closure}}")
+    }
+
+    /// Splits a `Result` or an `Option` into its success and failure cases.
+    pub trait IntoResult {
+        type Ok;
+        type Failed;
+
+        fn into_result(self) -> Result<Self::Ok, Self::Failed>;
+    }
+
+    impl<T, E> IntoResult for Result<T, E> {
+        type Ok = T;
+        type Failed = ErrValue<E>;
+
+        #[inline]
+        fn into_result(self) -> Result<T, ErrValue<E>> {
+            self.map_err(ErrValue)
+        }
+    }
+
+    impl<T> IntoResult for Option<T> {
+        type Ok = T;
+        type Failed = NoneValue;
+
+        #[inline]
+        fn into_result(self) -> Result<T, NoneValue> {
+            self.ok_or(NoneValue)
+        }
+    }
+
+    /// The content of an `Err`.
+    pub struct ErrValue<E>(E);
+
+    /// A `None`.
+    pub struct NoneValue;
+
+    /// The failure case of a `Result` or an `Option`.
+    pub trait Failed {
+        fn unwrap_failed<L>(self, log: L, source: &Source, kv: &dyn slog::KV) -> !
+        where
+            L: AsLogger;
+
+        fn expect_failed<L>(
+            self,
+            log: L,
+            source: &Source,
+            msg: &dyn fmt::Display,
+            kv: &dyn slog::KV,
+        ) -> !
+        where
+            L: AsLogger;
+    }
+
+    impl<E: fmt::Debug> Failed for ErrValue<E> {
+        #[inline]
+        #[track_caller]
+        fn unwrap_failed<L>(self, log: L, source: &Source, kv: &dyn slog::KV) -> !
+        where
+            L: AsLogger,
+        {
+            failed(
+                Failure::new(
+                    Target::Logger(log.as_logger()),
+                    default_level(),
+                    "unwrap_or_log!",
+                    "Err",
+                    &"called `unwrap_or_log!()` on an `Err` value",
+                )
+                .with_value(&self.0)
+                .with_kv(kv)
+                .with_source(source),
+            )
+        }
+
+        #[inline]
+        #[track_caller]
+        fn expect_failed<L>(
+            self,
+            log: L,
+            source: &Source,
+            msg: &dyn fmt::Display,
+            kv: &dyn slog::KV,
+        ) -> !
+        where
+            L: AsLogger,
+        {
+            failed(
+                Failure::new(
+                    Target::Logger(log.as_logger()),
+                    default_level(),
+                    "expect_or_log!",
+                    "Err",
+                    msg,
+                )
+                .with_value(&self.0)
+                .with_kv(kv)
+                .with_source(source),
+            )
+        }
+    }
+
+    impl Failed for NoneValue {
+        #[inline]
+        #[track_caller]
+        fn unwrap_failed<L>(self, log: L, source: &Source, kv: &dyn slog::KV) -> !
+        where
+            L: AsLogger,
+        {
+            failed(
+                Failure::new(
+                    Target::Logger(log.as_logger()),
+                    default_level(),
+                    "unwrap_or_log!",
+                    "None",
+                    &"called `unwrap_or_log!()` on a `None` value",
+                )
+                .with_kv(kv)
+                .with_source(source),
+            )
+        }
+
+        #[inline]
+        #[track_caller]
+        fn expect_failed<L>(
+            self,
+            log: L,
+            source: &Source,
+            msg: &dyn fmt::Display,
+            kv: &dyn slog::KV,
+        ) -> !
+        where
+            L: AsLogger,
+        {
+            failed(
+                Failure::new(
+                    Target::Logger(log.as_logger()),
+                    default_level(),
+                    "expect_or_log!",
+                    "None",
+                    msg,
+                )
+                .with_kv(kv)
+                .with_source(source),
+            )
+        }
+    }
+}
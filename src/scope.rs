@@ -1,227 +1,34 @@
-use std::fmt;
-
-//
-// Extension trait for Result types.
-//
-
-/// Extension trait for Result types.
-pub trait ResultExt<T, E> {
-    /// Unwraps a result, yielding the content of an [`Ok`].
-    ///
-    /// # Panics
-    ///
-    /// Panics if the value is an [`Err`], logging a message provided by the
-    /// [`Err`]'s value to a scoped [`slog::Logger`] at a [`Critical`] level.
-    ///
-    /// [`Critical`]: /slog/2/slog/enum.Level.html#variant.Critical
-    fn unwrap_or_log(self) -> T
-    where
-        E: fmt::Debug;
-
-    /// Unwraps a result, yielding the content of an [`Ok`].
-    ///
-    /// # Panics
-    ///
-    /// Panics if the value is an [`Err`], logging the passed message and the
-    /// content of the [`Err`] to a scoped [`slog::Logger`] at a [`Critical`]
-    /// level.
-    ///
-    /// [`Critical`]: /slog/2/slog/enum.Level.html#variant.Critical
-    fn expect_or_log(self, msg: &str) -> T
-    where
-        E: fmt::Debug;
-
-    /// Unwraps a result, yielding the content of an [`Err`].
-    ///
-    /// # Panics
-    ///
-    /// Panics if the value is an [`Ok`], logging a message provided by the
-    /// [`Ok`]'s value to a scoped [`slog::Logger`] at a [`Critical`] level.
-    ///
-    /// [`Critical`]: /slog/2/slog/enum.Level.html#variant.Critical
-    fn unwrap_err_or_log(self) -> E
-    where
-        T: fmt::Debug;
-
-    /// Unwraps a result, yielding the content of an [`Err`].
-    ///
-    /// # Panics
-    ///
-    /// Panics if the value is an [`Ok`], logging the passed message and the
-    /// content of the [`Ok`] to a scoped [`slog::Logger`] at a [`Critical`]
-    /// level.
-    ///
-    /// [`Critical`]: /slog/2/slog/enum.Level.html#variant.Critical
-    fn expect_err_or_log(self, msg: &str) -> E
-    where
-        T: fmt::Debug;
-}
-
-impl<T, E> ResultExt<T, E> for Result<T, E> {
-    #[inline]
-    #[track_caller]
-    fn unwrap_or_log(self) -> T
-    where
-        E: fmt::Debug,
-    {
-        match self {
-            Ok(t) => t,
-            Err(e) => failed_with("called `Result::unwrap_or_log()` on an `Err` value", &e),
-        }
-    }
-
-    #[inline]
-    #[track_caller]
-    fn expect_or_log(self, msg: &str) -> T
-    where
-        E: fmt::Debug,
-    {
-        match self {
-            Ok(t) => t,
-            Err(e) => failed_with(msg, &e),
-        }
-    }
-
-    #[inline]
-    #[track_caller]
-    fn unwrap_err_or_log(self) -> E
-    where
-        T: fmt::Debug,
-    {
-        match self {
-            Ok(t) => failed_with("called `Result::unwrap_err_or_log()` on an `Ok` value", &t),
-            Err(e) => e,
-        }
-    }
-
-    #[inline]
-    #[track_caller]
-    fn expect_err_or_log(self, msg: &str) -> E
-    where
-        T: fmt::Debug,
-    {
-        match self {
-            Ok(t) => failed_with(msg, &t),
-            Err(e) => e,
-        }
-    }
-}
-
-//
-// Extension trait for Option types.
-//
-
-// Extension trait for Option types.
-pub trait OptionExt<T> {
-    /// Moves the value `v` out of the `Option<T>` if it is [`Some(v)`].
-    ///
-    /// In general, because this function may panic, its use is discouraged.
-    /// Instead, prefer to use pattern matching and handle the [`None`]
-    /// case explicitly.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the self value equals [`None`], logging an error message to a
-    /// scoped [`slog::Logger`] at a [`Critical`] level.
-    fn unwrap_or_log(self) -> T;
-
-    /// Unwraps an option, yielding the content of a [`Some`].
-    ///
-    /// # Panics
-    ///
-    /// Panics if the value is a [`None`], logging the passed message to a
-    /// scoped [`slog::Logger`] at a [`Critical`] level.
-    fn expect_or_log(self, msg: &str) -> T;
-
-    /// Unwraps an option, expecting [`None`] and returning nothing.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the value is a [`Some`], logging a message derived from the
-    /// [`Some`]'s value to a scoped [`slog::Logger`] at a [`Critical`] level.
-    fn unwrap_none_or_log(self)
-    where
-        T: fmt::Debug;
-
-    /// Unwraps an option, expecting [`None`] and returning nothing.
-    ///
-    /// # Panics
-    ///
-    /// Panics if the value is a [`Some`], logging the passed message and the
-    /// content of the [`Some`] to a scoped [`slog::Logger`] at a [`Critical`]
-    /// level.
-    fn expect_none_or_log(self, msg: &str)
-    where
-        T: fmt::Debug;
-}
-
-impl<T> OptionExt<T> for Option<T> {
-    fn unwrap_or_log(self) -> T {
-        match self {
-            Some(val) => val,
-            None => failed("called `Option::unwrap_or_log()` on a `None` value"),
-        }
-    }
-
-    #[inline]
-    #[track_caller]
-    fn expect_or_log(self, msg: &str) -> T {
-        match self {
-            Some(val) => val,
-            None => failed(msg),
-        }
-    }
-
-    #[inline]
-    #[track_caller]
-    fn unwrap_none_or_log(self)
-    where
-        T: fmt::Debug,
-    {
-        if let Some(val) = self {
-            failed_with(
-                "called `Option::unwrap_none_or_log()` on a `Some` value",
-                &val,
-            );
-        }
-    }
-
-    #[inline]
-    #[track_caller]
-    fn expect_none_or_log(self, msg: &str)
-    where
-        T: fmt::Debug,
-    {
-        if let Some(val) = self {
-            failed_with(msg, &val);
-        }
-    }
-}
-
-//
-// Helper functions.
-//
-
-#[inline(never)]
-#[cold]
-#[track_caller]
-fn failed(msg: &str) -> ! {
-    slog_scope::crit!("{}", msg);
-
-    #[cfg(feature = "panic-quiet")]
-    panic!();
-    #[cfg(not(feature = "panic-quiet"))]
-    panic!("{}", msg)
-}
-
-#[inline(never)]
-#[cold]
-#[track_caller]
-fn failed_with(msg: &str, value: &dyn fmt::Debug) -> ! {
-    slog_scope::crit!("{}: {:?}", msg, &value);
-
-    #[cfg(feature = "panic-quiet")]
-    panic!();
-    #[cfg(not(feature = "panic-quiet"))]
-    panic!("{}: {:?}", msg, &value);
+//! Extension traits logging to the logger set in
+//! [`slog-scope`](https://github.com/slog-rs/scope).
+//!
+//! The traits have the same methods as the crate's root
+//! [`ResultExt`](crate::ResultExt) and [`OptionExt`](crate::OptionExt), minus
+//! the `log` argument. Import them from this module instead of those:
+//!
+//! ```should_panic
+//! use slog_unwrap::scope::OptionExt;
+//!
+//! let log = slog::Logger::root(slog::Discard, slog::o!());
+//! slog_scope::scope(&log, || {
+//!     None::<u32>.expect_or_log("no session");
+//! });
+//! ```
+
+use crate::ext::ext_traits;
+use crate::failure::Target;
+
+ext_traits! {
+    /// Extension trait for Result types, logging to the logger set in
+    /// [`slog-scope`](https://github.com/slog-rs/scope).
+    pub trait ResultExt;
+
+    /// Extension trait for Option types, logging to the logger set in
+    /// [`slog-scope`](https://github.com/slog-rs/scope).
+    pub trait OptionExt;
+
+    target: Target::Scope,
+    to: "to a scoped [`slog::Logger`]",
+    level: slog::Level,
+    kv: slog::KV => with_kv,
+    kv_doc: "`kv` is typically built with [`slog::b!`], and only serialized if the unwrap fails.",
 }
@@ -0,0 +1,431 @@
+//! Helpers for testing that code logs the failed unwraps it is expected to.
+//!
+//! [`capture_failures`] runs a closure with a [`slog::Logger`] that records
+//! into a [`CapturingDrain`], and returns the records of the unwraps that
+//! failed within it. [`assert_logged_failure!`](crate::assert_logged_failure)
+//! then checks those records for a given failure.
+//!
+//! A [`FailureCollector`] instead runs a closure in intercept mode, where
+//! failed unwraps are recorded rather than logged, and those that would
+//! panic can end the closure with a fallback value.
+//!
+//! ```
+//! use slog_unwrap::assert_logged_failure;
+//! use slog_unwrap::testing::capture_failures;
+//! use slog_unwrap::ResultExt;
+//!
+//! let records = capture_failures(|log| {
+//!     let not_great: Result<(), _> = Result::Err(42);
+//!     not_great.expect_or_log(log, "not terrible");
+//! });
+//!
+//! assert_logged_failure!(records, msg = "not terrible", value = 42);
+//! ```
+//!
+//! This module is only available with the `testing` feature.
+
+use crate::failure::Failure;
+use crate::handler;
+use crate::kv::KeyValues;
+use slog::{Drain, OwnedKVList, Record};
+use std::fmt::{self, Write};
+use std::panic::{self, AssertUnwindSafe, Location};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// A drain that keeps every record logged to it in memory.
+///
+/// Clones of a drain share the same records, so a clone can be given to a
+/// [`slog::Logger`] and the original kept to inspect them.
+#[derive(Clone, Debug, Default)]
+pub struct CapturingDrain {
+    records: Arc<Mutex<Vec<CapturedRecord>>>,
+}
+
+impl CapturingDrain {
+    /// Creates a drain with no records.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a [`slog::Logger`] that logs to a clone of this drain.
+    pub fn logger(&self) -> slog::Logger {
+        slog::Logger::root(self.clone(), slog::o!())
+    }
+
+    /// Returns a copy of the records logged so far.
+    pub fn records(&self) -> Vec<CapturedRecord> {
+        self.lock().clone()
+    }
+
+    /// Returns a copy of the records of failed unwraps logged so far. See
+    /// [`CapturedRecord::is_failure`].
+    pub fn failures(&self) -> Vec<CapturedRecord> {
+        self.lock()
+            .iter()
+            .filter(|record| record.is_failure())
+            .cloned()
+            .collect()
+    }
+
+    /// Discards the records logged so far.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<CapturedRecord>> {
+        self.records
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Drain for CapturingDrain {
+    type Ok = ();
+    type Err = slog::Never;
+
+    fn log(&self, record: &Record, values: &OwnedKVList) -> Result<(), slog::Never> {
+        self.lock().push(CapturedRecord {
+            level: record.level(),
+            msg: record.msg().to_string(),
+            file: record.file(),
+            line: record.line(),
+            column: record.column(),
+            kv: KeyValues::of(record, values).0,
+        });
+        Ok(())
+    }
+}
+
+/// A record kept by a [`CapturingDrain`], with its message and values
+/// formatted as strings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CapturedRecord {
+    level: slog::Level,
+    msg: String,
+    file: &'static str,
+    line: u32,
+    column: u32,
+    kv: Vec<(String, String)>,
+}
+
+impl CapturedRecord {
+    /// The level the record was logged at.
+    pub fn level(&self) -> slog::Level {
+        self.level
+    }
+
+    /// The record's message.
+    pub fn msg(&self) -> &str {
+        &self.msg
+    }
+
+    /// The file the record was logged from. For failed unwraps, this is the
+    /// file of the call that failed.
+    pub fn file(&self) -> &'static str {
+        self.file
+    }
+
+    /// The line the record was logged from.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    /// The column the record was logged from.
+    pub fn column(&self) -> u32 {
+        self.column
+    }
+
+    /// Returns the value of `key`, if the record or its logger has it.
+    ///
+    /// Keys of the record take precedence over those of its logger.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.kv
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns every key and value of the record, followed by those of its
+    /// logger.
+    pub fn kv(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.kv.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Whether the record is that of a failed unwrap, recognized by its
+    /// `method` and `variant` keys.
+    pub fn is_failure(&self) -> bool {
+        self.get("method").is_some() && self.get("variant").is_some()
+    }
+}
+
+/// Runs `f` with a [`slog::Logger`] that records into a [`CapturingDrain`],
+/// and returns the records of the unwraps that failed within it.
+///
+/// While `f` runs, failed unwraps on the current thread are logged and then
+/// unwind, without calling the panic hook, regardless of any
+/// [`FailureHandler`](crate::FailureHandler) or
+/// [`FailureAction`](crate::FailureAction) set. `f` is run under
+/// [`catch_unwind`](std::panic::catch_unwind), so such an unwind ends `f` but
+/// not the test. Other panics are resumed once `f` has returned.
+pub fn capture_failures<F>(f: F) -> Vec<CapturedRecord>
+where
+    F: FnOnce(&slog::Logger),
+{
+    let drain = CapturingDrain::new();
+    let log = drain.logger();
+
+    let result = {
+        let _guard = handler::scoped_thread_handler(Arc::new(|failure: &Failure| {
+            failure.log();
+            if failure.is_fatal() {
+                // Unwinds without calling the panic hook.
+                panic::resume_unwind(Box::new(UnwrapFailed));
+            }
+        }));
+        panic::catch_unwind(AssertUnwindSafe(|| f(&log)))
+    };
+
+    if let Err(payload) = result {
+        if !payload.is::<UnwrapFailed>() {
+            panic::resume_unwind(payload);
+        }
+    }
+    drain.failures()
+}
+
+/// The payload of the panics of failed unwraps within [`capture_failures`].
+struct UnwrapFailed;
+
+/// Collects the failed unwraps on the current thread, instead of logging
+/// them, while a closure runs in intercept mode.
+///
+/// In intercept mode, failed unwraps that don't panic, such as
+/// [`ok_or_log`](crate::ResultExt::ok_or_log), are recorded and carry on as
+/// usual. Those that do panic are recorded and then either unwind with the
+/// [`InterceptedFailure`] as payload, with [`intercept`](Self::intercept),
+/// or end the closure early with a fallback value, with
+/// [`intercept_or_else`](Self::intercept_or_else). This holds regardless of
+/// any [`FailureHandler`](crate::FailureHandler) or
+/// [`FailureAction`](crate::FailureAction) set.
+///
+/// Intercept mode applies to every logger, scoped or not. It can be nested;
+/// the previous mode is restored when a closure returns or panics.
+///
+/// # Examples
+///
+/// ```
+/// use slog_unwrap::testing::FailureCollector;
+/// use slog_unwrap::{OptionExt, ResultExt};
+///
+/// let log = slog::Logger::root(slog::Discard, slog::o!());
+/// let collector = FailureCollector::new();
+///
+/// let total = collector.intercept_or_else(
+///     || {
+///         let first: Result<u32, _> = Result::Err("bad input");
+///         let second: Option<u32> = None;
+///         first.ok_or_log(&log).unwrap_or(1) + second.expect_or_log(&log, "no second")
+///     },
+///     |_failure| 0,
+/// );
+///
+/// assert_eq!(total, 0);
+/// let failures = collector.failures();
+/// assert_eq!(failures[0].value(), Some("\"bad input\""));
+/// assert_eq!(failures[1].msg(), "no second");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct FailureCollector {
+    failures: Arc<Mutex<Vec<InterceptedFailure>>>,
+}
+
+impl FailureCollector {
+    /// Creates a collector with no failures.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns a copy of the failures collected so far.
+    pub fn failures(&self) -> Vec<InterceptedFailure> {
+        self.lock().clone()
+    }
+
+    /// Discards the failures collected so far.
+    pub fn clear(&self) {
+        self.lock().clear();
+    }
+
+    /// Runs `f` in intercept mode, where failed unwraps that would panic
+    /// instead unwind with an [`InterceptedFailure`] as payload, without
+    /// calling the panic hook.
+    ///
+    /// The payload can be recovered from the [`Err`] returned by
+    /// [`catch_unwind`](std::panic::catch_unwind) with
+    /// `downcast::<InterceptedFailure>()`.
+    pub fn intercept<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let collector = self.clone();
+        let _guard = handler::scoped_thread_handler(Arc::new(move |failure: &Failure| {
+            let failure = InterceptedFailure::new(failure);
+            collector.lock().push(failure.clone());
+            if failure.is_fatal() {
+                // Unwinds without calling the panic hook.
+                panic::resume_unwind(Box::new(failure));
+            }
+        }));
+        f()
+    }
+
+    /// Runs `f` in intercept mode, where the first failed unwrap that would
+    /// panic instead ends `f`, whose result is then that of `fallback`.
+    ///
+    /// Panics that don't come from a failed unwrap are resumed.
+    pub fn intercept_or_else<F, G, R>(&self, f: F, fallback: G) -> R
+    where
+        F: FnOnce() -> R,
+        G: FnOnce(&InterceptedFailure) -> R,
+    {
+        match panic::catch_unwind(AssertUnwindSafe(|| self.intercept(f))) {
+            Ok(result) => result,
+            Err(payload) => match payload.downcast::<InterceptedFailure>() {
+                Ok(failure) => fallback(&failure),
+                Err(payload) => panic::resume_unwind(payload),
+            },
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<InterceptedFailure>> {
+        self.failures
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// A failed unwrap recorded in intercept mode by a [`FailureCollector`],
+/// with its message and value formatted as strings.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct InterceptedFailure {
+    location: &'static Location<'static>,
+    level: slog::Level,
+    method: &'static str,
+    variant: &'static str,
+    msg: String,
+    value: Option<String>,
+    fatal: bool,
+}
+
+impl InterceptedFailure {
+    fn new(failure: &Failure) -> Self {
+        InterceptedFailure {
+            location: failure.location(),
+            level: failure.level(),
+            method: failure.method(),
+            variant: failure.variant(),
+            msg: failure.msg().to_string(),
+            value: failure.value().map(|value| format!("{:?}", value)),
+            fatal: failure.is_fatal(),
+        }
+    }
+
+    /// The location of the call that failed.
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// The level the failure would have been logged at.
+    pub fn level(&self) -> slog::Level {
+        self.level
+    }
+
+    /// The name of the method that was called, e.g. `unwrap_or_log`.
+    pub fn method(&self) -> &'static str {
+        self.method
+    }
+
+    /// The variant that was found, e.g. `Err` or `None`.
+    pub fn variant(&self) -> &'static str {
+        self.variant
+    }
+
+    /// The message describing the failure.
+    pub fn msg(&self) -> &str {
+        &self.msg
+    }
+
+    /// The offending value, as it would have been logged, if there is one.
+    pub fn value(&self) -> Option<&str> {
+        self.value.as_deref()
+    }
+
+    /// Whether the method that was called would have panicked.
+    pub fn is_fatal(&self) -> bool {
+        self.fatal
+    }
+}
+
+/// Asserts that `records` contain a failed unwrap with the given message and
+/// values. Not public API, see
+/// [`assert_logged_failure!`](crate::assert_logged_failure).
+#[doc(hidden)]
+#[track_caller]
+pub fn assert_logged_failure(records: &[CapturedRecord], expected: &[(&str, &dyn fmt::Display)]) {
+    let expected: Vec<(&str, String)> = expected
+        .iter()
+        .map(|(key, value)| (*key, value.to_string()))
+        .collect();
+
+    let found = records.iter().any(|record| {
+        record.is_failure()
+            && expected.iter().all(|(key, value)| match *key {
+                "msg" => record.msg() == value,
+                key => record.get(key) == Some(value.as_str()),
+            })
+    });
+
+    if !found {
+        let mut msg = String::from("no logged failure matches");
+        for (key, value) in &expected {
+            let _ = write!(msg, " {} = {:?}", key, value);
+        }
+        let _ = write!(msg, "\nlogged failures: {:#?}", records);
+        panic!("{}", msg);
+    }
+}
+
+/// Asserts that a list of [`CapturedRecord`]s contains a failed unwrap
+/// matching every given `key = value` pair.
+///
+/// The `msg` key matches the record's message, and any other key the value
+/// of that key in the record, such as `value`, `method` or `variant`. The
+/// expected values are compared to the logged ones as formatted with
+/// [`fmt::Display`].
+///
+/// # Panics
+///
+/// Panics if none of the records matches, listing them.
+///
+/// # Examples
+///
+/// ```
+/// use slog_unwrap::assert_logged_failure;
+/// use slog_unwrap::testing::capture_failures;
+/// use slog_unwrap::OptionExt;
+///
+/// let records = capture_failures(|log| {
+///     let nothing: Option<()> = None;
+///     nothing.unwrap_or_log(log);
+/// });
+///
+/// assert_logged_failure!(records, method = "unwrap_or_log", variant = "None");
+/// ```
+#[macro_export]
+macro_rules! assert_logged_failure {
+    ($records:expr $(, $key:ident = $value:expr)* $(,)?) => {
+        $crate::testing::assert_logged_failure(
+            ::core::convert::AsRef::<[$crate::testing::CapturedRecord]>::as_ref(&$records),
+            &[$((::core::stringify!($key), &$value as &dyn ::core::fmt::Display)),*],
+        )
+    };
+}
@@ -0,0 +1,70 @@
+//! Extension traits logging as [`tracing`](https://docs.rs/tracing) events
+//! within the current span.
+//!
+//! The traits have the same methods as the crate's root
+//! [`ResultExt`](crate::ResultExt) and [`OptionExt`](crate::OptionExt), minus
+//! the `log` argument, and take a `tracing::Level` and [`Fields`] in place of
+//! their `slog` counterparts. Import them from this module instead of those:
+//!
+//! ```should_panic
+//! use slog_unwrap::tracing_events::OptionExt;
+//!
+//! None::<u32>.expect_or_log_kv("no session", [("user", 42)]);
+//! ```
+
+use crate::ext::ext_traits;
+use crate::failure::Target;
+use std::fmt;
+
+/// Key-value pairs passed to the `_kv` methods, such as an array or slice of
+/// `(key, value)` pairs whose values implement [`fmt::Display`].
+///
+/// As the fields of a `tracing` event must be known at compile time, the
+/// pairs are recorded together as the event's `kv` field, formatted as
+/// `key: value, key: value`.
+pub trait Fields {
+    /// Calls `f` with each key and value.
+    fn visit(&self, f: &mut dyn FnMut(&'static str, &dyn fmt::Display));
+}
+
+impl<V: fmt::Display> Fields for [(&'static str, V)] {
+    fn visit(&self, f: &mut dyn FnMut(&'static str, &dyn fmt::Display)) {
+        for (key, value) in self {
+            f(key, value);
+        }
+    }
+}
+
+impl<V: fmt::Display, const N: usize> Fields for [(&'static str, V); N] {
+    fn visit(&self, f: &mut dyn FnMut(&'static str, &dyn fmt::Display)) {
+        self[..].visit(f)
+    }
+}
+
+impl<F: Fields + ?Sized> Fields for &F {
+    fn visit(&self, f: &mut dyn FnMut(&'static str, &dyn fmt::Display)) {
+        (**self).visit(f)
+    }
+}
+
+ext_traits! {
+    /// Extension trait for Result types, logging as `tracing` events within the
+    /// current span.
+    ///
+    /// The events have the `slog_unwrap` target, which is what `tracing`
+    /// filters such as `EnvFilter` directives must name to match them.
+    pub trait ResultExt;
+
+    /// Extension trait for Option types, logging as `tracing` events within the
+    /// current span.
+    ///
+    /// The events have the `slog_unwrap` target, which is what `tracing`
+    /// filters such as `EnvFilter` directives must name to match them.
+    pub trait OptionExt;
+
+    target: Target::Tracing,
+    to: "as a `tracing` event",
+    level: tracing::Level,
+    kv: Fields => with_fields,
+    kv_doc: "`kv` can be any [`Fields`], such as an array of `(key, value)` pairs, and is only formatted if the unwrap fails. The pairs are recorded together as the event's `kv` field.",
+}
@@ -0,0 +1,72 @@
+use slog_unwrap::testing::capture_failures;
+use slog_unwrap::{ChainStyle, ResultExt};
+use std::error::Error;
+use std::fmt;
+
+#[derive(Debug)]
+struct Layer {
+    msg: &'static str,
+    source: Option<Box<Layer>>,
+}
+
+impl Layer {
+    fn chain(msgs: &[&'static str]) -> Self {
+        let (msg, rest) = msgs.split_first().unwrap();
+        Layer {
+            msg,
+            source: if rest.is_empty() {
+                None
+            } else {
+                Some(Box::new(Layer::chain(rest)))
+            },
+        }
+    }
+}
+
+impl fmt::Display for Layer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.msg)
+    }
+}
+
+impl Error for Layer {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.source.as_deref().map(|source| source as _)
+    }
+}
+
+#[test]
+fn chain_keys_are_numbered_from_the_first_source() {
+    let records = capture_failures(|log| {
+        Err::<(), _>(Layer::chain(&["top", "mid", "root"])).unwrap_or_log_chain(log);
+    });
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].get("cause.0"), Some("mid"));
+    assert_eq!(records[0].get("cause.1"), Some("root"));
+    assert_eq!(records[0].get("cause.2"), None);
+
+    slog_unwrap::set_chain_style(ChainStyle::MultiLine);
+    let records = capture_failures(|log| {
+        Err::<(), _>(Layer::chain(&["top", "mid", "root"])).unwrap_or_log_chain(log);
+    });
+    slog_unwrap::set_chain_style(ChainStyle::Compact);
+    assert_eq!(
+        records[0].get("chain"),
+        Some("top\n\nCaused by:\n    0: mid\n    1: root")
+    );
+    assert_eq!(records[0].get("cause.0"), Some("mid"));
+}
+
+#[test]
+fn chain_keys_stop_at_cause_15() {
+    let msgs = [
+        "e0", "e1", "e2", "e3", "e4", "e5", "e6", "e7", "e8", "e9", "e10", "e11", "e12", "e13",
+        "e14", "e15", "e16", "e17",
+    ];
+    let records = capture_failures(|log| {
+        Err::<(), _>(Layer::chain(&msgs)).unwrap_or_log_chain(log);
+    });
+    assert_eq!(records[0].get("cause.15"), Some("e16"));
+    assert_eq!(records[0].get("cause.16"), None);
+    assert!(records[0].get("chain").unwrap().ends_with("e17"));
+}
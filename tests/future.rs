@@ -0,0 +1,89 @@
+use slog_unwrap::testing::CapturingDrain;
+use slog_unwrap::WithUnwrapLoggerExt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll, Wake, Waker};
+
+/// A future that is pending on its first poll, so that the code after it
+/// runs in a later poll.
+struct YieldOnce(bool);
+
+impl Future for YieldOnce {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<()> {
+        if self.0 {
+            Poll::Ready(())
+        } else {
+            self.0 = true;
+            Poll::Pending
+        }
+    }
+}
+
+struct NoopWaker;
+
+impl Wake for NoopWaker {
+    fn wake(self: Arc<Self>) {}
+}
+
+/// Polls `future` to completion, checking that it is pending in between with
+/// `between`.
+fn block_on<F: Future>(future: F, mut between: impl FnMut()) -> F::Output {
+    let mut future = Box::pin(future);
+    let waker = Waker::from(Arc::new(NoopWaker));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => between(),
+        }
+    }
+}
+
+#[test]
+fn global_option_ext_logs_to_the_task_logger() {
+    use slog_unwrap::global::OptionExt;
+
+    let task_drain = CapturingDrain::new();
+    let other_drain = CapturingDrain::new();
+    let task = async {
+        YieldOnce(false).await;
+        None::<u32>.unwrap_or_default_log()
+    };
+    let task = task.with_unwrap_logger(task_drain.logger().new(slog::o!("request" => 42)));
+
+    let output = slog_unwrap::with_logger(&other_drain.logger(), || {
+        block_on(task, || {
+            // Between polls, failures go to the thread's logger again.
+            None::<u32>.unwrap_or_default_log();
+        })
+    });
+
+    assert_eq!(output, 0);
+    let failures = task_drain.failures();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].get("method"), Some("unwrap_or_default_log"));
+    assert_eq!(failures[0].get("request"), Some("42"));
+    assert_eq!(other_drain.failures().len(), 1);
+}
+
+#[cfg(feature = "scope")]
+#[test]
+fn scope_option_ext_logs_to_the_task_logger() {
+    use slog_unwrap::scope::OptionExt;
+
+    let task_drain = CapturingDrain::new();
+    let task = async {
+        YieldOnce(false).await;
+        None::<u32>.unwrap_or_default_log()
+    };
+    let task = task.with_unwrap_logger(task_drain.logger().new(slog::o!("request" => 42)));
+
+    assert_eq!(block_on(task, || {}), 0);
+    let failures = task_drain.failures();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].get("method"), Some("unwrap_or_default_log"));
+    assert_eq!(failures[0].get("request"), Some("42"));
+}
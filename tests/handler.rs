@@ -0,0 +1,43 @@
+use slog_unwrap::testing::CapturingDrain;
+use slog_unwrap::{Failure, OptionExt};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+/// Returns a handler that records `name` for every failure it handles.
+fn handler(name: &'static str, handled: &Arc<Mutex<Vec<&'static str>>>) -> impl Fn(&Failure) {
+    let handled = handled.clone();
+    move |_: &Failure| handled.lock().unwrap().push(name)
+}
+
+// Handlers are process-wide, so they are only set within this one test.
+#[test]
+fn thread_handler_takes_precedence_over_global_handler() {
+    let drain = CapturingDrain::new();
+    let log = drain.logger();
+    let handled = Arc::new(Mutex::new(Vec::new()));
+    let fail = || {
+        None::<u8>.unwrap_or_default_log(&log);
+    };
+
+    slog_unwrap::set_failure_handler(handler("global", &handled));
+    fail();
+    slog_unwrap::set_thread_failure_handler(handler("thread", &handled));
+    fail();
+    // Other threads still use the global handler.
+    thread::scope(|scope| {
+        scope.spawn(fail);
+    });
+    slog_unwrap::clear_thread_failure_handler();
+    fail();
+    assert_eq!(
+        *handled.lock().unwrap(),
+        ["global", "thread", "global", "global"]
+    );
+    assert!(drain.records().is_empty());
+
+    // Back to the default handler, which logs the failure.
+    slog_unwrap::clear_failure_handler();
+    fail();
+    assert_eq!(handled.lock().unwrap().len(), 4);
+    assert_eq!(drain.failures().len(), 1);
+}
@@ -0,0 +1,63 @@
+use slog::Level;
+use slog_unwrap::testing::capture_failures;
+use slog_unwrap::{OptionExt, ResultExt};
+
+fn levels(f: impl FnOnce(&slog::Logger)) -> Vec<Level> {
+    capture_failures(f)
+        .iter()
+        .map(|record| record.level())
+        .collect()
+}
+
+// The default and fallback levels are process-wide, so they are only changed
+// within this one test.
+#[test]
+fn levels_follow_the_defaults_unless_given() {
+    assert_eq!(
+        levels(|log| {
+            let _ = None::<u8>.unwrap_or_log(log);
+        }),
+        [Level::Critical]
+    );
+    assert_eq!(
+        levels(|log| {
+            let _ = Err::<u8, _>(1).unwrap_or_default_log(log);
+        }),
+        [Level::Warning]
+    );
+    assert_eq!(
+        levels(|log| {
+            let _ = None::<u8>.expect_or_log_at(log, Level::Error, "no session");
+        }),
+        [Level::Error]
+    );
+    assert_eq!(
+        levels(|log| {
+            let _ = Err::<u8, _>(1).log_err(log, Level::Info);
+        }),
+        [Level::Info]
+    );
+
+    slog_unwrap::set_default_level(Level::Error);
+    slog_unwrap::set_fallback_level(Level::Info);
+    assert_eq!(
+        levels(|log| {
+            let _ = None::<u8>.unwrap_or_log(log);
+        }),
+        [Level::Error]
+    );
+    assert_eq!(
+        levels(|log| {
+            let _ = Err::<u8, _>(1).unwrap_or_default_log(log);
+        }),
+        [Level::Info]
+    );
+    assert_eq!(
+        levels(|log| {
+            let _ = None::<u8>.unwrap_or_log_at(log, Level::Warning);
+        }),
+        [Level::Warning]
+    );
+    assert_eq!(slog_unwrap::default_level(), Level::Error);
+    assert_eq!(slog_unwrap::fallback_level(), Level::Info);
+}
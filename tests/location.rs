@@ -0,0 +1,52 @@
+use slog_unwrap::testing::capture_failures;
+use std::panic::Location;
+
+/// Records the location it is called from, as the methods do.
+trait Locate {
+    #[track_caller]
+    fn locate(&self, location: &mut Option<&'static Location<'static>>) {
+        *location = Some(Location::caller());
+    }
+}
+
+impl<T> Locate for Option<T> {}
+
+#[test]
+fn records_have_the_caller_location() {
+    use slog_unwrap::OptionExt;
+
+    let mut expected = None;
+    let records = capture_failures(|log| {
+        None::<u8>.locate(&mut expected);
+        None::<u8>.unwrap_or_log(log);
+    });
+
+    let expected = expected.unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].file(), expected.file());
+    assert_eq!(records[0].line(), expected.line() + 1);
+    assert_eq!(records[0].column(), expected.column());
+}
+
+#[cfg(feature = "scope")]
+#[test]
+fn scoped_records_have_the_caller_location() {
+    use slog_unwrap::scope::OptionExt;
+    use slog_unwrap::testing::CapturingDrain;
+
+    let drain = CapturingDrain::new();
+    let mut expected = None;
+    capture_failures(|_| {
+        slog_scope::scope(&drain.logger(), || {
+            None::<u8>.locate(&mut expected);
+            None::<u8>.unwrap_or_log();
+        })
+    });
+
+    let expected = expected.unwrap();
+    let records = drain.failures();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].file(), expected.file());
+    assert_eq!(records[0].line(), expected.line() + 1);
+    assert_eq!(records[0].column(), expected.column());
+}
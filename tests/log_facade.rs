@@ -0,0 +1,97 @@
+use slog_unwrap::log_facade::{OptionExt, ResultExt};
+use std::panic;
+use std::sync::Mutex;
+
+/// A record passed to the `log` facade, with its message and key-values
+/// formatted as strings.
+struct Logged {
+    level: log::Level,
+    target: String,
+    msg: String,
+    file: Option<String>,
+    line: Option<u32>,
+    kv: Vec<(String, String)>,
+}
+
+impl Logged {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.kv
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+struct Capture(Mutex<Vec<Logged>>);
+
+impl log::Log for Capture {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        struct Pairs(Vec<(String, String)>);
+
+        impl<'kvs> log::kv::VisitSource<'kvs> for Pairs {
+            fn visit_pair(
+                &mut self,
+                key: log::kv::Key<'kvs>,
+                value: log::kv::Value<'kvs>,
+            ) -> Result<(), log::kv::Error> {
+                self.0.push((key.to_string(), value.to_string()));
+                Ok(())
+            }
+        }
+
+        let mut kv = Pairs(Vec::new());
+        record.key_values().visit(&mut kv).unwrap();
+        self.0.lock().unwrap().push(Logged {
+            level: record.level(),
+            target: record.target().to_owned(),
+            msg: record.args().to_string(),
+            file: record.file().map(str::to_owned),
+            line: record.line(),
+            kv: kv.0,
+        });
+    }
+
+    fn flush(&self) {}
+}
+
+static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+
+// The `log` logger is process-wide, so it is only used within this one test.
+#[test]
+fn failures_are_logged_through_the_log_facade() {
+    log::set_logger(&CAPTURE).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+    // Regardless of the `abort` and `exit` features.
+    slog_unwrap::set_failure_action(slog_unwrap::FailureAction::Panic);
+
+    let line = line!() + 2;
+    let result = panic::catch_unwind(|| {
+        None::<u32>.expect_or_log_kv("no session", [("user", 42)]);
+    });
+    assert!(result.is_err());
+    assert_eq!(Err::<u32, _>("missing").unwrap_or_default_log(), 0);
+    let _ = None::<u32>.log_none(log::Level::Info);
+
+    let logged = CAPTURE.0.lock().unwrap();
+    assert_eq!(logged.len(), 3);
+
+    assert_eq!(logged[0].level, log::Level::Error);
+    assert_eq!(logged[0].target, "slog_unwrap");
+    assert_eq!(logged[0].msg, "no session");
+    assert_eq!(logged[0].file.as_deref(), Some(file!()));
+    assert_eq!(logged[0].line, Some(line));
+    assert_eq!(logged[0].get("user"), Some("42"));
+    assert_eq!(logged[0].get("method"), Some("expect_or_log_kv"));
+    assert_eq!(logged[0].get("variant"), Some("None"));
+
+    assert_eq!(logged[1].level, log::Level::Warn);
+    assert_eq!(logged[1].get("value"), Some("\"missing\""));
+    assert_eq!(logged[1].get("method"), Some("unwrap_or_default_log"));
+
+    assert_eq!(logged[2].level, log::Level::Info);
+    assert_eq!(logged[2].get("method"), Some("log_none"));
+}
@@ -0,0 +1,44 @@
+use slog_unwrap::testing::capture_failures;
+use slog_unwrap::{assert_logged_failure, expect_or_log, unwrap_or_log};
+
+fn load_config(log: &slog::Logger) {
+    let path: Result<&str, _> = Err("missing");
+    unwrap_or_log!(path, log; "user" => 42);
+}
+
+#[test]
+fn unwrap_or_log_records_the_expression_and_function() {
+    let records = capture_failures(load_config);
+    assert_logged_failure!(
+        records,
+        value = "\"missing\"",
+        expr = "path",
+        module = module_path!(),
+        function = concat!(module_path!(), "::load_config"),
+        user = 42,
+    );
+}
+
+#[test]
+fn expect_or_log_records_the_message_and_expression() {
+    let records = capture_failures(|log| {
+        let request = 7;
+        let session: Option<u32> = None;
+        expect_or_log!(
+            session.filter(|id| *id > 0),
+            log,
+            "no session for {}",
+            request
+        );
+    });
+    assert_logged_failure!(
+        records,
+        msg = "no session for 7",
+        expr = "session.filter(|id| *id > 0)",
+        module = module_path!(),
+        function = concat!(
+            module_path!(),
+            "::expect_or_log_records_the_message_and_expression"
+        ),
+    );
+}
@@ -0,0 +1,30 @@
+use slog_unwrap::testing::CapturingDrain;
+use slog_unwrap::OptionExt;
+use std::panic;
+
+#[test]
+fn reinstalled_panic_hook_logs_each_panic_once() {
+    // Regardless of the `abort` and `exit` features.
+    slog_unwrap::set_failure_action(slog_unwrap::FailureAction::Panic);
+
+    let first = CapturingDrain::new();
+    let second = CapturingDrain::new();
+    let unwraps = CapturingDrain::new();
+    let log = unwraps.logger();
+    slog_unwrap::install_panic_hook(first.logger());
+    slog_unwrap::install_panic_hook(second.logger());
+
+    let result = panic::catch_unwind(|| None::<u8>.unwrap_or_log(&log));
+    assert!(result.is_err());
+    assert_eq!(unwraps.failures().len(), 1);
+    assert!(first.records().is_empty());
+    assert!(second.records().is_empty());
+
+    let result = panic::catch_unwind(|| panic!("out of bounds"));
+    assert!(result.is_err());
+    assert!(first.records().is_empty());
+    let records = second.records();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].msg(), "out of bounds");
+    assert_eq!(records[0].level(), slog::Level::Critical);
+}
@@ -0,0 +1,116 @@
+use slog_unwrap::tracing_events::{OptionExt, ResultExt};
+use std::collections::HashMap;
+use std::fmt;
+use std::panic;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Metadata, Subscriber};
+
+/// An event, with its fields formatted as strings and the name of the span
+/// it was emitted in.
+struct Captured {
+    level: Level,
+    target: String,
+    span: Option<&'static str>,
+    fields: HashMap<&'static str, String>,
+}
+
+/// A subscriber that keeps every event in memory.
+#[derive(Clone, Default)]
+struct Capture(Arc<Inner>);
+
+#[derive(Default)]
+struct Inner {
+    next_id: AtomicU64,
+    spans: Mutex<HashMap<u64, &'static str>>,
+    entered: Mutex<Vec<u64>>,
+    events: Mutex<Vec<Captured>>,
+}
+
+struct Fields(HashMap<&'static str, String>);
+
+impl Visit for Fields {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.insert(field.name(), format!("{:?}", value));
+    }
+}
+
+impl Subscriber for Capture {
+    fn enabled(&self, _: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let id = self.0.next_id.fetch_add(1, Ordering::Relaxed) + 1;
+        self.0
+            .spans
+            .lock()
+            .unwrap()
+            .insert(id, span.metadata().name());
+        Id::from_u64(id)
+    }
+
+    fn record(&self, _: &Id, _: &Record<'_>) {}
+
+    fn record_follows_from(&self, _: &Id, _: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields(HashMap::new());
+        event.record(&mut fields);
+        let span = self.0.entered.lock().unwrap().last().copied();
+        self.0.events.lock().unwrap().push(Captured {
+            level: *event.metadata().level(),
+            target: event.metadata().target().to_owned(),
+            span: span.map(|id| self.0.spans.lock().unwrap()[&id]),
+            fields: fields.0,
+        });
+    }
+
+    fn enter(&self, span: &Id) {
+        self.0.entered.lock().unwrap().push(span.into_u64());
+    }
+
+    fn exit(&self, _: &Id) {
+        self.0.entered.lock().unwrap().pop();
+    }
+}
+
+#[test]
+fn failures_are_events_within_the_current_span() {
+    // Regardless of the `abort` and `exit` features.
+    slog_unwrap::set_failure_action(slog_unwrap::FailureAction::Panic);
+
+    let capture = Capture::default();
+    let line = line!() + 5;
+    tracing::subscriber::with_default(capture.clone(), || {
+        let span = tracing::info_span!("request");
+        let _entered = span.enter();
+        let result = panic::catch_unwind(|| {
+            None::<u32>.expect_or_log_kv("no session", [("user", 42)]);
+        });
+        assert!(result.is_err());
+        assert_eq!(Err::<u32, _>("missing").unwrap_or_default_log(), 0);
+        let _ = None::<u32>.log_none(Level::INFO);
+    });
+
+    let events = capture.0.events.lock().unwrap();
+    assert_eq!(events.len(), 3);
+
+    assert_eq!(events[0].level, Level::ERROR);
+    assert_eq!(events[0].target, "slog_unwrap");
+    assert_eq!(events[0].span, Some("request"));
+    assert_eq!(events[0].fields["message"], "no session");
+    assert_eq!(events[0].fields["method"], "expect_or_log_kv");
+    assert_eq!(events[0].fields["variant"], "None");
+    assert_eq!(events[0].fields["kv"], "user: 42");
+    assert!(events[0].fields["location"].starts_with(&format!("{}:{}:", file!(), line)));
+
+    assert_eq!(events[1].level, Level::WARN);
+    assert_eq!(events[1].fields["value"], "\"missing\"");
+    assert_eq!(events[1].fields["method"], "unwrap_or_default_log");
+
+    assert_eq!(events[2].level, Level::INFO);
+    assert_eq!(events[2].fields["method"], "log_none");
+}
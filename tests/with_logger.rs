@@ -0,0 +1,42 @@
+use slog_unwrap::global::OptionExt;
+use slog_unwrap::testing::CapturingDrain;
+use std::panic::{self, AssertUnwindSafe};
+
+#[test]
+fn nested_with_logger_restores_the_outer_logger() {
+    let outer = CapturingDrain::new();
+    let inner = CapturingDrain::new();
+
+    slog_unwrap::with_logger(&outer.logger(), || {
+        slog_unwrap::with_logger(&inner.logger(), || {
+            None::<u32>.unwrap_or_default_log();
+        });
+        None::<u32>.unwrap_or_default_log();
+    });
+
+    assert_eq!(inner.failures().len(), 1);
+    assert_eq!(outer.failures().len(), 1);
+}
+
+#[test]
+fn nested_with_logger_restores_the_outer_logger_on_panic() {
+    // Regardless of the `abort` and `exit` features.
+    slog_unwrap::set_failure_action(slog_unwrap::FailureAction::Panic);
+    let outer = CapturingDrain::new();
+    let inner = CapturingDrain::new();
+
+    slog_unwrap::with_logger(&outer.logger(), || {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            slog_unwrap::with_logger(&inner.logger(), || {
+                None::<u32>.expect_or_log("no session");
+            })
+        }));
+        assert!(result.is_err());
+        None::<u32>.unwrap_or_default_log();
+    });
+
+    assert_eq!(inner.failures().len(), 1);
+    let failures = outer.failures();
+    assert_eq!(failures.len(), 1);
+    assert_eq!(failures[0].get("method"), Some("unwrap_or_default_log"));
+}